@@ -0,0 +1,46 @@
+use rayon::prelude::*;
+use crate::algorithm::network_diversion::{network_diversion, DiversionResult};
+use crate::algorithm::shortest_odd_path::OddPathSolver;
+use crate::structure::error::Error;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::graph::planar_edge::PlanarEdge;
+use crate::structure::graph::planar_graph::PlanarGraph;
+use crate::structure::path_result::PathResult;
+use crate::structure::weight::Weight;
+
+/**
+Answer many shortest odd path queries on the same graph in parallel, with one result per (s, t) query in the same order.
+
+The graph is shared between all the threads. The queries are split into one chunk per thread, and every chunk is answered by an OddPathSolver of its own,
+so the mirror graph is only built once per chunk rather than once per query.
+A query fails just like try_shortest_odd_path, without stopping the others.
+*/
+pub fn shortest_odd_path_batch<W, E, G>(graph: &G, queries: &[(usize, usize)]) -> Vec<Result<PathResult<W,E>, Error>>
+    where W: Weight + Send + Sync,
+          E: Edge<W> + Send + Sync,
+          G: Graph<W,E> + Sync,
+{
+    let chunk = queries.len().div_ceil(rayon::current_num_threads()).max(1);
+    queries.par_chunks(chunk)
+        .flat_map_iter(|chunk| {
+            let mut solver = OddPathSolver::new(graph);
+            chunk.iter()
+                .map(|&(s, t)| solver.try_query(s, t))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/**
+Answer many network diversion queries on the same planar graph in parallel, with one result per (s, t, d) query in the same order.
+Both the real graph and its dual are shared between all the threads.
+*/
+pub fn network_diversion_batch<W, G>(planar: &PlanarGraph<W,G>, queries: &[(usize, usize, (usize, usize))]) -> Vec<Result<DiversionResult<W>, Error>>
+    where W: Weight + Send + Sync,
+          G: Graph<W, PlanarEdge<W>> + Sync,
+{
+    queries.par_iter()
+        .map(|&(s, t, d)| network_diversion(planar, s, t, d))
+        .collect()
+}
@@ -0,0 +1,120 @@
+use crate::algorithm::shortest_path::bfs_forest;
+use crate::structure::bipartition::Bipartition::{self, *};
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::weight::Weight;
+use crate::utility::logging::debug;
+
+/**
+Problem: Bipartiteness Testing
+In: an undirected graph G
+Out: a 2-colouring of G if it is bipartite, otherwise an odd cycle in G
+
+The colouring is true for every vertex on the odd side, so that every edge goes between a true and a false vertex.
+In a bipartite graph every path between two vertices of the same colour is even, so the colouring explains why no odd path could be found.
+
+The colours are the parities of the depths in a breadth first forest.
+An edge between two vertices of the same colour closes an odd cycle together with the tree paths up to their common ancestor.
+Picking the edge closest to its root keeps the cycle short, it has at most 2d+1 edges if the edge is at depth d.
+*/
+pub fn bipartition<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> Bipartition<E> {
+    let (dist, pred) = bfs_forest(graph);
+    let depth = |u: usize| dist[u].unwrap();
+    let conflict = graph.vertices()
+        .flat_map(|u| graph.neighbours(u))
+        .filter(|e| depth(e.from()) % 2 == depth(e.to()) % 2)
+        .min_by_key(|e| depth(e.from()));
+
+    match conflict {
+        None => Bipartite {
+            colouring: graph.vertices().map(|u| depth(u) % 2 == 1).collect(),
+        },
+        Some(e) => {
+            // Both ends are equally deep, so we can climb the tree from both at the same pace until they meet.
+            let (mut down, mut up) = (Vec::new(), Vec::new());
+            let (mut a, mut b) = (e.from(), e.to());
+            while a != b {
+                let (pa, pb) = (pred[a].clone().unwrap(), pred[b].clone().unwrap());
+                a = pa.from();
+                b = pb.from();
+                down.push(pa);
+                up.push(pb.reverse());
+            }
+            down.reverse();
+            down.push(e);
+            down.extend(up);
+            debug!("Found an odd cycle of length {}", down.len());
+            NonBipartite { odd_cycle: down }
+        }
+    }
+}
+
+pub fn is_bipartite<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> bool {
+    matches!(bipartition(graph), Bipartite {..})
+}
+
+#[cfg(test)]
+mod test_bipartite {
+    use std::fs::read_to_string;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize,usize)]) -> UndirectedGraph<u64, BasicEdge<u64>> {
+        let mut graph = UndirectedGraph::new(n);
+        for &(u,v) in edges {
+            graph.add_edge(BasicEdge::new(u, v, 1));
+        }
+        graph
+    }
+
+    fn read(name: &str) -> UndirectedGraph<u64, BasicEdge<u64>> {
+        read_to_string(["data/special_graphs/", name, "/", name, ".in"].concat()).unwrap().parse().unwrap()
+    }
+
+    fn assert_colouring(graph: &UndirectedGraph<u64, BasicEdge<u64>>) {
+        match bipartition(graph) {
+            NonBipartite { odd_cycle } => panic!("Found an odd cycle in a bipartite graph: {:?}", odd_cycle),
+            Bipartite { colouring } => {
+                assert_eq!(graph.n(), colouring.len());
+                for e in graph.edges() {
+                    assert_ne!(colouring[e.from()], colouring[e.to()], "The edge {:?} has the same colour at both ends", e);
+                }
+            }
+        }
+    }
+
+    fn assert_odd_cycle(graph: &UndirectedGraph<u64, BasicEdge<u64>>, length: usize) {
+        match bipartition(graph) {
+            Bipartite {..} => panic!("Found a 2-colouring of a graph with an odd cycle"),
+            NonBipartite { odd_cycle } => {
+                assert_eq!(length, odd_cycle.len(), "Expected an odd cycle of length {}, but got {:?}", length, odd_cycle);
+                assert_eq!(odd_cycle[0].from(), odd_cycle[length-1].to(), "The cycle {:?} is not closed", odd_cycle);
+                for (i, e) in odd_cycle.iter().enumerate() {
+                    assert!(graph.is_adjacent(e.from(), e.to()), "The cycle uses the edge {:?}, which is not in the graph", e);
+                    assert_eq!(e.to(), odd_cycle[(i+1) % length].from(), "The cycle {:?} is not connected", odd_cycle);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bipartite_graphs() {
+        assert_colouring(&graph(0, &[]));
+        assert_colouring(&graph(3, &[]));
+        assert_colouring(&graph(6, &[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 4)]));
+        assert_colouring(&read("grid5"));
+        assert!(is_bipartite(&read("grid5")));
+    }
+
+    #[test]
+    fn test_odd_cycles() {
+        assert_odd_cycle(&graph(1, &[(0, 0)]), 1);
+        assert_odd_cycle(&graph(3, &[(0, 1), (1, 2), (2, 0)]), 3);
+        // The triangle is in the second component, far away from its root.
+        assert_odd_cycle(&graph(8, &[(0, 1), (3, 4), (4, 5), (5, 6), (6, 7), (7, 5)]), 3);
+        assert_odd_cycle(&read("petersen"), 5);
+        assert_odd_cycle(&read("gamma4"), 3);
+        assert!(! is_bipartite(&read("petersen")));
+    }
+}
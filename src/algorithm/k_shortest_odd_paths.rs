@@ -0,0 +1,160 @@
+use crate::algorithm::shortest_even_path::shortest_even_path;
+use crate::algorithm::shortest_odd_path::shortest_odd_path;
+use crate::structure::graph::csr_graph::CsrGraph;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::path_result::{PathResult, PathResult::*};
+use crate::structure::weight::Weight;
+use crate::utility::logging::debug;
+use crate::utility::misc::repeat;
+
+/**
+Problem: K Shortest Odd Paths
+In: an undirected graph G, two vertices s,t in V(G), and a number k
+Out: the k cheapest simple s-t-paths in G using an odd number of edges, in order of increasing cost, or all of them if there are fewer.
+
+The paths are found lazily, one at a time, so asking for a large k only costs as much as the paths that are actually used.
+A path is told apart from the others by its vertices and the weights of its edges, so parallel edges of the same weight give the same path only once, even if their labels differ.
+*/
+pub fn k_shortest_odd_paths<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize, k: usize) -> impl Iterator<Item = PathResult<W,E>> {
+    OddPathEnumerator::new(graph, s, t).take(k)
+}
+
+/**
+Yen's algorithm, with Lawler's improvement, enumerating the simple odd s-t-paths from the cheapest one and up.
+
+Every new path branches off a path found earlier at some spur vertex v: it follows the earlier path from s to v, and then deviates.
+The rest of the path is the shortest v-t-path that avoids the vertices before v, and every edge out of v that a path found so far with the same start has taken.
+It has to be odd if the start has an even number of edges, and even otherwise, for the whole path to be odd.
+Only the spur vertices from where a path branched off its parent and onwards can give new paths, the earlier ones have been tried already.
+*/
+pub struct OddPathEnumerator<W: Weight, E: Edge<W>> {
+    graph: CsrGraph<W,E>,
+    s: usize,
+    t: usize,
+    started: bool,
+    exhausted: bool,
+    // The paths found so far, each with the index where it branched off its parent.
+    found: Vec<(Vec<E>, usize)>,
+    candidates: Vec<(W, Vec<E>, usize)>,
+}
+
+impl <W: Weight, E: Edge<W>> OddPathEnumerator<W,E> {
+    pub fn new<G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> Self {
+        OddPathEnumerator {
+            graph: CsrGraph::from_neighbourhoods(graph.vertices().map(|u| graph.neighbours(u))),
+            s,
+            t,
+            started: false,
+            exhausted: false,
+            found: Vec::new(),
+            candidates: Vec::new(),
+        }
+    }
+
+    // Find every path that branches off the latest path found, from its own branching point and onwards.
+    fn branch(&mut self) {
+        let (path, deviation) = self.found.last().unwrap().clone();
+        for i in deviation..path.len() {
+            let root = &path[..i];
+            let spur = path[i].from();
+            let banned: Vec<E> = self.found.iter()
+                .filter(|(other, _)| other.len() > i && same_path(&other[..i], root))
+                .map(|(other, _)| other[i].clone())
+                .collect();
+            let mut removed = repeat(self.graph.n(), false);
+            for e in root {
+                removed[e.from()] = true;
+            }
+
+            let rest = SpurGraph {
+                graph: &self.graph,
+                removed,
+                banned,
+            };
+            let result = if i % 2 == 0 {
+                shortest_odd_path(&rest, spur, self.t)
+            }
+            else {
+                shortest_even_path(&rest, spur, self.t)
+            };
+
+            if let Possible {path: tail, ..} = result {
+                let candidate: Vec<E> = root.iter().cloned().chain(tail).collect();
+                let known = self.candidates.iter().any(|(_, other, _)| same_path(other, &candidate))
+                    || self.found.iter().any(|(other, _)| same_path(other, &candidate));
+                if ! known {
+                    let cost = candidate.iter().fold(W::zero(), |cost, e| cost + e.weight());
+                    self.candidates.push((cost, candidate, i));
+                }
+            }
+        }
+    }
+}
+
+// Two edges are the same if they have the same endpoints and weight, labels are not taken into account.
+fn same_edge<W: Weight, E: Edge<W>>(a: &E, b: &E) -> bool {
+    a.from() == b.from() && a.to() == b.to() && a.weight() == b.weight()
+}
+
+fn same_path<W: Weight, E: Edge<W>>(p: &[E], q: &[E]) -> bool {
+    p.len() == q.len() && p.iter().zip(q).all(|(a, b)| same_edge(a, b))
+}
+
+/**
+The graph that the rest of a new path is searched for in, with the vertices of the root and the banned edges out of the spur vertex hidden.
+A banned edge hides every edge between the same two vertices with the same weight, in either direction, whatever their labels.
+*/
+struct SpurGraph<'a, W: Weight, E: Edge<W>> {
+    graph: &'a CsrGraph<W,E>,
+    removed: Vec<bool>,
+    banned: Vec<E>,
+}
+
+impl <'a, W: Weight, E: Edge<W>> SpurGraph<'a,W,E> {
+    fn hides(&self, e: &E) -> bool {
+        self.removed[e.from()]
+            || self.removed[e.to()]
+            || self.banned.iter().any(|b| same_edge(b, e) || same_edge(&b.reverse(), e))
+    }
+}
+
+impl <'a, W: Weight, E: Edge<W>> Graph<W,E> for SpurGraph<'a,W,E> {
+    fn n(&self) -> usize { self.graph.n() }
+    // Every edge is seen from both ends, so this counts each one twice.
+    fn m(&self) -> usize {
+        self.vertices().map(|u| self.neighbours(u).count()).sum::<usize>() / 2
+    }
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = E> + '_ {
+        self.graph.neighbours(u).filter(|e| ! self.hides(e))
+    }
+}
+
+impl <W: Weight, E: Edge<W>> Iterator for OddPathEnumerator<W,E> {
+    type Item = PathResult<W,E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if ! self.started {
+            self.started = true;
+            if let Possible {cost, path} = shortest_odd_path(&self.graph, self.s, self.t) {
+                self.found.push((path.clone(), 0));
+                return Some(Possible {cost, path});
+            }
+            return None;
+        }
+        if self.found.is_empty() || self.exhausted {
+            return None;
+        }
+
+        self.branch();
+        let Some(cheapest) = (0..self.candidates.len())
+            .reduce(|i, j| if self.candidates[j].0 < self.candidates[i].0 { j } else { i }) else {
+            self.exhausted = true;
+            return None;
+        };
+        let (cost, path, deviation) = self.candidates.swap_remove(cheapest);
+        debug!("Found odd path number {} of cost {}, branching off at edge {}", self.found.len() + 1, cost, deviation);
+        self.found.push((path.clone(), deviation));
+        Some(Possible {cost, path})
+    }
+}
@@ -1,6 +1,14 @@
 mod utility;
 pub mod shortest_odd_walk;
 pub mod shortest_odd_path;
+pub mod shortest_even_path;
 pub mod shortest_bottleneck_path;
+pub mod shortest_odd_cycle;
+pub mod shortest_odd_labelled_path;
+pub mod k_shortest_odd_paths;
 pub mod network_diversion;
+pub mod planarity;
+pub mod bipartite;
 pub mod shortest_path;
+#[cfg(feature = "rayon")]
+pub mod batch;
@@ -1,63 +1,209 @@
+use std::fmt::{Display, Formatter};
 use queues::{IsQueue, Queue};
-use crate::algorithm::shortest_odd_path::shortest_odd_path;
+use crate::algorithm::shortest_odd_path::try_shortest_odd_path;
 use crate::algorithm::utility::split_edges;
+use crate::structure::error::{Error, Error::*};
 use crate::structure::graph::edge::Edge;
 use crate::structure::path_result::{PathResult::*};
+use crate::structure::graph::csr_graph::CsrGraph;
 use crate::structure::graph::planar_edge::PlanarEdge;
 use crate::structure::graph::planar_graph::PlanarGraph;
-use crate::structure::graph::undirected_graph::UndirectedGraph;
+use crate::structure::graph::Graph;
 use crate::structure::weight::Weight;
-use crate::utility::misc::{assert_is_path, debug, repeat};
+use crate::utility::misc::repeat;
+use crate::utility::logging::debug;
+use DiversionResult::*;
+use NoDiversion::*;
+
+// The cheapest diversion set and its total cost, or why there is none.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiversionResult<W: Weight> {
+    Diverted {
+        cost: W,
+        diversion: Vec<PlanarEdge<W>>,
+    },
+    Undivertable {
+        reason: NoDiversion,
+    },
+}
+
+impl <W: Weight> DiversionResult<W> {
+    pub fn cost(&self) -> Option<W> {
+        match self {
+            Diverted {cost, ..} => Some(cost.clone()),
+            Undivertable {..} => None,
+        }
+    }
+}
+
+// Why no set of edges can force the s-t-paths through the diversion edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoDiversion {
+    // s = t, so the empty path can never be diverted.
+    SameVertex,
+    // None of the s-t-paths use any of the diversion edges.
+    NoPathThrough,
+}
+
+impl Display for NoDiversion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SameVertex => write!(f, "s and t are the same vertex, so the empty path between them can not be diverted"),
+            NoPathThrough => write!(f, "none of the paths from s to t go through any of the diversion edges"),
+        }
+    }
+}
 
 /**
 Problem: Network Diversion
 In: an planar graph G, two vertices s and t, and edge d
 Out: the cheapest set of edges to delete from the graph such that all s-t-paths must pass through d, if one exists
+
+Fails if s, t or d are not in the graph, or if the cost of the diversion grows too large for W.
 */
 
-pub fn network_diversion<W: Weight>(planar: &PlanarGraph<W>, s: usize, t: usize, (du, dv): (usize,usize)) -> Option<(W, Vec<PlanarEdge<W>>)> {
+pub fn network_diversion<W: Weight, G: Graph<W, PlanarEdge<W>>>(planar: &PlanarGraph<W,G>, s: usize, t: usize, (du, dv): (usize,usize)) -> Result<DiversionResult<W>, Error> {
+    for vertex in [s, t, du, dv] {
+        if vertex >= planar.n() {
+            return Err(VertexOutOfRange {vertex, n: planar.n()});
+        }
+    }
+    let diversion = planar.real().neighbours(du)
+        .find(|l| l.to() == dv)
+        .ok_or(MissingEdge {from: du, to: dv})?;
+    if s == t {
+        return Ok(Undivertable {reason: SameVertex});
+    }
     if let Some(p) = st_path_without_d(planar.real(), s, t, (du, dv)) {
         let path = p.iter()
             .map(|e| e.rotate_right())
             .collect();
-        let diversion = planar.real().N(du).iter()
-            .find(|l| l.to() == dv)
-            .expect("The diversion edge doesn't exist")
-            .clone();
         let (split, map) = split_edges(planar.dual(), path);
-        match shortest_odd_path(&split, diversion.left(), diversion.right()) {
+        match try_shortest_odd_path(&split, diversion.left(), diversion.right())? {
             Impossible => {
-                debug(format!("No diversion set exist, no paths from {} to {} go through ({}, {}).", s, t, du, dv));
-                None
+                debug!("No diversion set exist, no paths from {} to {} go through ({}, {}).", s, t, du, dv);
+                Ok(Undivertable {reason: NoPathThrough})
             }
             Possible {cost, path} => {
                 let mapped: Vec<PlanarEdge<W>> = path.iter().flat_map(|e| map(e)).collect();
                 let rotated: Vec<PlanarEdge<W>> = mapped.iter().map(|e| e.rotate_right()).collect();
 
-                // TODO: delete this when the bug is fixed
-                assert_is_path(&path);
-                assert_is_path(&mapped);
-                
-                debug(format!("We have to cut {} edges to divert the network, with a total cost of {}.", path.len(), cost));
+                debug!("We have to cut {} edges to divert the network, with a total cost of {}.", path.len(), cost);
                 if path.len() < 15 {
-                    debug(format!("Dual diversion set: {:?}", mapped));
-                    debug(format!("Real diversion set: {:?}\n", rotated));
+                    debug!("Dual diversion set: {:?}", mapped);
+                    debug!("Real diversion set: {:?}", rotated);
                 }
 
-                Some((
+                Ok(Diverted {
                     cost,
-                    rotated,
-                ))
+                    diversion: rotated,
+                })
             }
         }
     }
     else {
-        debug("Could not find any s-t-path that doesn't use the diversion edge, no diversion is needed.".to_string());
-        Some((0.into(), Vec::new()))
+        debug!("Could not find any s-t-path that doesn't use the diversion edge, no diversion is needed.");
+        Ok(Diverted {cost: W::zero(), diversion: Vec::new()})
+    }
+}
+
+/**
+Problem: Network Diversion through a set of edges
+In: a planar graph G, two vertices s and t, and a set of edges D
+Out: the cheapest set of edges outside D to delete from the graph such that all s-t-paths must pass through at least one edge in D, if one exists
+
+Every minimal diversion set is a cycle through the dual of one of the edges in D, so the dual edges of D cost nothing,
+and we look for the cheapest such cycle with one shortest odd path per edge in D.
+
+Fails if s, t or any edge in D are not in the graph, or if the cost of the diversion grows too large for W.
+*/
+pub fn multi_network_diversion<W: Weight, G: Graph<W, PlanarEdge<W>>>(planar: &PlanarGraph<W,G>, s: usize, t: usize, diversions: &[(usize,usize)]) -> Result<DiversionResult<W>, Error> {
+    let mut duals = Vec::new();
+    for &(du, dv) in diversions {
+        for vertex in [s, t, du, dv] {
+            if vertex >= planar.n() {
+                return Err(VertexOutOfRange {vertex, n: planar.n()});
+            }
+        }
+        let diversion = planar.real().neighbours(du)
+            .find(|l| l.to() == dv)
+            .ok_or(MissingEdge {from: du, to: dv})?;
+        duals.push(diversion.rotate_right());
+    }
+    if s == t {
+        return Ok(Undivertable {reason: SameVertex});
+    }
+    let Some(p) = st_path_without(planar.real(), s, t, diversions) else {
+        debug!("Could not find any s-t-path that avoids all the diversion edges, no diversion is needed.");
+        return Ok(Diverted {cost: W::zero(), diversion: Vec::new()});
+    };
+
+    // The dual edges of D are exactly those that separate the two endpoints of an edge in D.
+    let is_diversion = |e: &PlanarEdge<W>| diversions.iter().any(|&(du, dv)| (e.left(), e.right()) == (du, dv) || (e.left(), e.right()) == (dv, du));
+    let free: CsrGraph<W, PlanarEdge<W>> = CsrGraph::from_neighbourhoods(planar.dual().vertices().map(|u| {
+        planar.dual().neighbours(u).map(|mut e| {
+            if is_diversion(&e) {
+                e.weight = W::zero();
+            }
+            e
+        })
+    }));
+    let path = p.iter()
+        .map(|e| e.rotate_right())
+        .collect();
+    let (split, map) = split_edges(&free, path);
+
+    let mut best: Option<(W, Vec<PlanarEdge<W>>)> = None;
+    for d in duals {
+        if let Possible {cost, path} = try_shortest_odd_path(&split, d.from(), d.to())? {
+            if best.as_ref().map_or(true, |(c, _)| cost < *c) {
+                let diversion = path.iter()
+                    .flat_map(&map)
+                    .filter(|e| ! is_diversion(e))
+                    .map(|e| e.rotate_right())
+                    .collect();
+                best = Some((cost, diversion));
+            }
+        }
     }
+    match best {
+        Some((cost, diversion)) => {
+            debug!("We have to cut {} edges to divert the network, with a total cost of {}.", diversion.len(), cost);
+            Ok(Diverted {cost, diversion})
+        }
+        None => {
+            debug!("No diversion set exist, no paths from {} to {} go through any of {:?}.", s, t, diversions);
+            Ok(Undivertable {reason: NoPathThrough})
+        }
+    }
+}
+
+/**
+Problem: Vertex Diversion
+In: a planar graph G, two vertices s and t, and a vertex v
+Out: the cheapest set of edges to delete from the graph such that all s-t-paths must pass through v, if one exists
+
+A path goes through v exactly when it uses one of the edges around v, and deleting those never helps,
+so this is the same as diverting the network through the set of edges around v.
+
+Fails if s, t or v are not in the graph, or if the cost of the diversion grows too large for W.
+*/
+pub fn vertex_diversion<W: Weight, G: Graph<W, PlanarEdge<W>>>(planar: &PlanarGraph<W,G>, s: usize, t: usize, v: usize) -> Result<DiversionResult<W>, Error> {
+    if v >= planar.n() {
+        return Err(VertexOutOfRange {vertex: v, n: planar.n()});
+    }
+    let around: Vec<(usize,usize)> = planar.real().neighbours(v)
+        .map(|e| (e.from(), e.to()))
+        .collect();
+    multi_network_diversion(planar, s, t, &around)
+}
+
+pub fn st_path_without_d<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize, d: (usize, usize)) -> Option<Vec<E>> {
+    st_path_without(graph, s, t, &[d])
 }
 
-pub fn st_path_without_d<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize, t: usize, (du,dv): (usize, usize)) -> Option<Vec<E>> {
+// Find any s-t-path that does not use any of the given edges.
+fn st_path_without<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize, banned: &[(usize, usize)]) -> Option<Vec<E>> {
     let mut seen = repeat(graph.n(), false);
     let mut prev: Vec<Option<E>> = repeat(graph.n(), None);
     let mut q: Queue<usize> = Queue::new();
@@ -65,12 +211,12 @@ pub fn st_path_without_d<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s:
     q.add(s).ok()?;
 
     while let Ok(u) = q.remove() {
-        for line in graph.N(u) {
+        for line in graph.neighbours(u) {
             let v = line.to();
-            if (u,v) != (du,dv) && (v,u) != (du,dv) && ! seen[v] {
+            if ! banned.iter().any(|&(du,dv)| (u,v) == (du,dv) || (v,u) == (du,dv)) && ! seen[v] {
                 seen[v] = true;
                 q.add(v).ok()?;
-                prev[v] = Some(line.clone());
+                prev[v] = Some(line);
                 if v == t {
                     break;
                 }
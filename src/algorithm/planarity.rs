@@ -0,0 +1,709 @@
+use crate::structure::error::{Error, Error::*};
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::planar_graph::PlanarGraph;
+use crate::structure::graph::simple_graph_strategy::SimpleGraphStrategy;
+use crate::structure::graph::undirected_graph::UndirectedGraph;
+use crate::structure::graph::Graph;
+use crate::structure::planarity::Planarity::{self, *};
+use crate::structure::weight::Weight;
+use crate::utility::misc::repeat;
+use crate::utility::logging::debug;
+
+/**
+Problem: Planarity Testing
+In: an undirected graph G
+Out: a combinatorial embedding of G in the plane if it is planar, otherwise a subgraph of G that is a subdivision of K5 or K3,3
+
+The embedding lists the neighbours of every vertex in clockwise order, and can be handed to PlanarGraph::from_rotation_system.
+Self-loops and parallel edges do not change whether a graph is planar, so they are ignored here.
+
+This is the left-right planarity test by Brandes, which runs in linear time.
+Finding the Kuratowski subgraph is slower: every edge is removed in turn, and put back if the rest of the graph became planar.
+*/
+pub fn planarity<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> Planarity<E> {
+    let n = graph.n();
+    let mut edges: Vec<E> = Vec::new();
+    let mut seen = repeat(n, usize::MAX);
+    for u in graph.vertices() {
+        for e in graph.neighbours(u) {
+            if u < e.to() && seen[e.to()] != u {
+                seen[e.to()] = u;
+                edges.push(e);
+            }
+        }
+    }
+    let endpoints = |edges: &[E]| edges.iter().map(|e| (e.from(), e.to())).collect::<Vec<_>>();
+
+    match LeftRight::new(n, &endpoints(&edges)).embedding() {
+        Some(rotation) => Planar { rotation },
+        None => {
+            let mut i = 0;
+            while i < edges.len() {
+                let e = edges.remove(i);
+                if LeftRight::new(n, &endpoints(&edges)).planar() {
+                    edges.insert(i, e);
+                    i += 1;
+                }
+            }
+            debug!("Found a Kuratowski subgraph with {} edges", edges.len());
+            NonPlanar { kuratowski: edges }
+        }
+    }
+}
+
+pub fn is_planar<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> bool {
+    let mut edges = Vec::new();
+    for u in graph.vertices() {
+        edges.extend(graph.neighbours(u).filter(|e| u < e.to()).map(|e| (u, e.to())));
+    }
+    LeftRight::new(graph.n(), &edges).planar()
+}
+
+/**
+Embed a graph without a given drawing, so that it can be used for network diversion.
+If the graph is not planar, the edges of a Kuratowski subgraph are returned in the error.
+Since a PlanarGraph must satisfy Euler's formula, the graph must also be connected and without self-loops.
+*/
+pub fn embed_planar<S: SimpleGraphStrategy, W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>) -> Result<PlanarGraph<W>, Error> {
+    match planarity(graph) {
+        Planar { rotation } => PlanarGraph::from_rotation_system::<S,E>(graph, &rotation),
+        NonPlanar { kuratowski } => Err(NotPlanar {
+            kuratowski: kuratowski.iter().map(|e| (e.from(), e.to())).collect(),
+        }),
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn new(e: usize) -> Self { Interval { low: Some(e), high: Some(e) } }
+    fn is_empty(&self) -> bool { self.low.is_none() && self.high.is_none() }
+}
+
+#[derive(Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) { std::mem::swap(&mut self.left, &mut self.right) }
+}
+
+/*
+The state of the left-right planarity test.
+The edges are given ids, and the DFS orients each of them from src to dst,
+so that tree edges point away from the root and back edges point towards it.
+All the searches use an explicit stack, since the DFS tree of a large sparse graph can be very deep.
+*/
+struct LeftRight {
+    n: usize,
+    m: usize,
+    adj: Vec<Vec<usize>>,
+    src: Vec<usize>,
+    dst: Vec<usize>,
+    oriented: Vec<bool>,
+    roots: Vec<usize>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<i64>,
+    outgoing: Vec<Vec<usize>>,
+    reference: Vec<Option<usize>>,
+    side: Vec<i64>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<Option<usize>>,
+}
+
+impl LeftRight {
+    fn new(n: usize, edges: &[(usize,usize)]) -> Self {
+        let m = edges.len();
+        let mut adj = repeat(n, Vec::new());
+        for (i, &(u,v)) in edges.iter().enumerate() {
+            adj[u].push(i);
+            adj[v].push(i);
+        }
+        LeftRight {
+            n,
+            m,
+            adj,
+            src: edges.iter().map(|&(u,_)| u).collect(),
+            dst: edges.iter().map(|&(_,v)| v).collect(),
+            oriented: repeat(m, false),
+            roots: Vec::new(),
+            height: repeat(n, None),
+            parent_edge: repeat(n, None),
+            lowpt: repeat(m, 0),
+            lowpt2: repeat(m, 0),
+            nesting_depth: repeat(m, 0),
+            outgoing: repeat(n, Vec::new()),
+            reference: repeat(m, None),
+            side: repeat(m, 1),
+            stack: Vec::new(),
+            stack_bottom: repeat(m, 0),
+            lowpt_edge: repeat(m, None),
+        }
+    }
+
+    fn planar(mut self) -> bool {
+        if self.n > 2 && self.m > 3 * self.n - 6 {
+            return false;
+        }
+        self.orient();
+        self.test()
+    }
+
+    fn embedding(mut self) -> Option<Vec<Vec<usize>>> {
+        if self.n > 2 && self.m > 3 * self.n - 6 {
+            return None;
+        }
+        self.orient();
+        let order = self.outgoing.clone();
+        if ! self.test() {
+            return None;
+        }
+        self.outgoing = order;
+        Some(self.embed())
+    }
+
+    // Orient the edges with a DFS, and compute the lowpoints and nesting depth of every edge.
+    fn orient(&mut self) {
+        for root in 0..self.n {
+            if self.height[root].is_some() {
+                continue;
+            }
+            self.height[root] = Some(0);
+            self.roots.push(root);
+            let mut stack = vec![(root, 0)];
+            while let Some((v, i)) = stack.last_mut() {
+                let v = *v;
+                if *i == self.adj[v].len() {
+                    stack.pop();
+                    if let Some(e) = self.parent_edge[v] {
+                        self.finish_orientation(e);
+                    }
+                    continue;
+                }
+                let e = self.adj[v][*i];
+                *i += 1;
+                if self.oriented[e] {
+                    continue;
+                }
+                self.oriented[e] = true;
+                let w = if self.src[e] == v { self.dst[e] } else { self.src[e] };
+                self.src[e] = v;
+                self.dst[e] = w;
+                self.outgoing[v].push(e);
+
+                let hv = self.height[v].unwrap();
+                self.lowpt[e] = hv;
+                self.lowpt2[e] = hv;
+                match self.height[w] {
+                    None => {
+                        self.parent_edge[w] = Some(e);
+                        self.height[w] = Some(hv + 1);
+                        stack.push((w, 0));
+                    }
+                    Some(hw) => {
+                        self.lowpt[e] = hw;
+                        self.finish_orientation(e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish_orientation(&mut self, e: usize) {
+        let v = self.src[e];
+        self.nesting_depth[e] = 2 * self.lowpt[e] as i64;
+        if self.lowpt2[e] < self.height[v].unwrap() {
+            self.nesting_depth[e] += 1;
+        }
+        if let Some(p) = self.parent_edge[v] {
+            if self.lowpt[e] < self.lowpt[p] {
+                self.lowpt2[p] = self.lowpt[p].min(self.lowpt2[e]);
+                self.lowpt[p] = self.lowpt[e];
+            }
+            else if self.lowpt[e] > self.lowpt[p] {
+                self.lowpt2[p] = self.lowpt2[p].min(self.lowpt[e]);
+            }
+            else {
+                self.lowpt2[p] = self.lowpt2[p].min(self.lowpt2[e]);
+            }
+        }
+    }
+
+    // Try to assign every back edge to the left or right side of the DFS tree without any conflicts.
+    fn test(&mut self) -> bool {
+        for v in 0..self.n {
+            let mut outgoing = std::mem::take(&mut self.outgoing[v]);
+            outgoing.sort_by_key(|&e| self.nesting_depth[e]);
+            self.outgoing[v] = outgoing;
+        }
+        for r in 0..self.roots.len() {
+            let mut stack = vec![(self.roots[r], 0)];
+            while let Some((v, i)) = stack.last_mut() {
+                let v = *v;
+                if *i == self.outgoing[v].len() {
+                    stack.pop();
+                    if let Some(e) = self.parent_edge[v] {
+                        self.remove_back_edges(e);
+                        if ! self.integrate(e) {
+                            return false;
+                        }
+                    }
+                    continue;
+                }
+                let e = self.outgoing[v][*i];
+                *i += 1;
+                self.stack_bottom[e] = self.stack.len();
+                let w = self.dst[e];
+                if self.parent_edge[w] == Some(e) {
+                    stack.push((w, 0));
+                }
+                else {
+                    self.lowpt_edge[e] = Some(e);
+                    self.stack.push(ConflictPair { left: Interval::default(), right: Interval::new(e) });
+                    if ! self.integrate(e) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    // Integrate the return edges of e into the constraints of its parent edge.
+    fn integrate(&mut self, e: usize) -> bool {
+        let v = self.src[e];
+        match self.parent_edge[v] {
+            Some(p) if self.lowpt[e] < self.height[v].unwrap() => {
+                if self.outgoing[v][0] == e {
+                    self.lowpt_edge[p] = self.lowpt_edge[e];
+                    true
+                }
+                else {
+                    self.add_constraints(e, p)
+                }
+            }
+            _ => true,
+        }
+    }
+
+    fn conflicting(&self, interval: &Interval, e: usize) -> bool {
+        interval.high.is_some_and(|h| self.lowpt[h] > self.lowpt[e])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(r)) => self.lowpt[r],
+            (Some(l), None) => self.lowpt[l],
+            (Some(l), Some(r)) => self.lowpt[l].min(self.lowpt[r]),
+            (None, None) => unreachable!("Empty conflict pairs are never pushed to the stack"),
+        }
+    }
+
+    fn add_constraints(&mut self, e: usize, parent: usize) -> bool {
+        let mut p = ConflictPair::default();
+
+        // Merge the return edges of e into the right interval.
+        while let Some(mut q) = self.stack.pop() {
+            if ! q.left.is_empty() {
+                q.swap();
+            }
+            if ! q.left.is_empty() {
+                return false;
+            }
+            let low = q.right.low.unwrap();
+            if self.lowpt[low] > self.lowpt[parent] {
+                if p.right.is_empty() {
+                    p.right = q.right;
+                }
+                else {
+                    self.reference[p.right.low.unwrap()] = q.right.high;
+                }
+                p.right.low = q.right.low;
+            }
+            else {
+                self.reference[low] = self.lowpt_edge[parent];
+            }
+            if self.stack.len() == self.stack_bottom[e] {
+                break;
+            }
+        }
+
+        // Merge the conflicting return edges of the earlier siblings of e into the left interval.
+        while let Some(top) = self.stack.last() {
+            if ! self.conflicting(&top.left, e) && ! self.conflicting(&top.right, e) {
+                break;
+            }
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(&q.right, e) {
+                q.swap();
+            }
+            if self.conflicting(&q.right, e) {
+                return false;
+            }
+            if let Some(low) = p.right.low {
+                self.reference[low] = q.right.high;
+            }
+            if q.right.low.is_some() {
+                p.right.low = q.right.low;
+            }
+            if p.left.is_empty() {
+                p.left = q.left;
+            }
+            else if let Some(low) = p.left.low {
+                self.reference[low] = q.left.high;
+            }
+            p.left.low = q.left.low;
+        }
+
+        if ! p.left.is_empty() || ! p.right.is_empty() {
+            self.stack.push(p);
+        }
+        true
+    }
+
+    // Remove the back edges that return to the tail of e, since they cannot conflict with anything above it.
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.src[e];
+        let hu = self.height[u].unwrap();
+        while let Some(top) = self.stack.last() {
+            if self.lowest(top) != hu {
+                break;
+            }
+            if let Some(low) = self.stack.pop().unwrap().left.low {
+                self.side[low] = -1;
+            }
+        }
+
+        if let Some(mut p) = self.stack.pop() {
+            while let Some(high) = p.left.high.filter(|&h| self.dst[h] == u) {
+                p.left.high = self.reference[high];
+            }
+            if p.left.high.is_none() {
+                if let Some(low) = p.left.low.take() {
+                    self.reference[low] = p.right.low;
+                    self.side[low] = -1;
+                }
+            }
+            while let Some(high) = p.right.high.filter(|&h| self.dst[h] == u) {
+                p.right.high = self.reference[high];
+            }
+            if p.right.high.is_none() {
+                if let Some(low) = p.right.low.take() {
+                    self.reference[low] = p.left.low;
+                    self.side[low] = -1;
+                }
+            }
+            self.stack.push(p);
+        }
+
+        // The side of e is the side of its highest return edge.
+        if self.lowpt[e] < hu {
+            if let Some(top) = self.stack.last() {
+                let (hl, hr) = (top.left.high, top.right.high);
+                self.reference[e] = match (hl, hr) {
+                    (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => hl,
+                    (Some(_), None) => hl,
+                    _ => hr,
+                };
+            }
+        }
+    }
+
+    // Resolve the side of e relative to the chain of edges it refers to.
+    fn sign(&mut self, e: usize) -> i64 {
+        let mut chain = vec![e];
+        while let Some(r) = self.reference[*chain.last().unwrap()] {
+            chain.push(r);
+        }
+        for i in (0..chain.len() - 1).rev() {
+            self.side[chain[i]] *= self.side[chain[i+1]];
+            self.reference[chain[i]] = None;
+        }
+        self.side[e]
+    }
+
+    // Turn the sides of the back edges into a clockwise rotation around every vertex.
+    fn embed(mut self) -> Vec<Vec<usize>> {
+        for e in 0..self.m {
+            self.nesting_depth[e] *= self.sign(e);
+        }
+        for v in 0..self.n {
+            let mut outgoing = std::mem::take(&mut self.outgoing[v]);
+            outgoing.sort_by_key(|&e| self.nesting_depth[e]);
+            self.outgoing[v] = outgoing;
+        }
+
+        let mut rotation = Rotation::new(self.n, self.m);
+        for v in 0..self.n {
+            let mut previous = None;
+            for &e in &self.outgoing[v] {
+                let h = self.half(v, e);
+                rotation.insert_after(v, h, previous);
+                previous = Some(h);
+            }
+        }
+
+        let mut left_ref = repeat(self.n, None);
+        let mut right_ref = repeat(self.n, None);
+        for &root in &self.roots {
+            let mut stack = vec![(root, 0)];
+            while let Some((v, i)) = stack.last_mut() {
+                let v = *v;
+                if *i == self.outgoing[v].len() {
+                    stack.pop();
+                    continue;
+                }
+                let e = self.outgoing[v][*i];
+                *i += 1;
+                let w = self.dst[e];
+                let h = self.half(w, e);
+                if self.parent_edge[w] == Some(e) {
+                    rotation.insert_first(w, h);
+                    left_ref[v] = Some(e);
+                    right_ref[v] = Some(e);
+                    stack.push((w, 0));
+                }
+                else if self.side[e] == 1 {
+                    rotation.insert_after(w, h, right_ref[w].map(|r| self.half(w, r)));
+                }
+                else {
+                    rotation.insert_before(w, h, left_ref[w].map(|l| self.half(w, l)));
+                    left_ref[w] = Some(e);
+                }
+            }
+        }
+
+        (0..self.n)
+            .map(|v| rotation.around(v).map(|h| self.opposite(h)).collect())
+            .collect()
+    }
+
+    // Every edge e is split in two half-edges, 2e leaving src[e] and 2e+1 leaving dst[e].
+    fn half(&self, v: usize, e: usize) -> usize { 2 * e + (self.src[e] != v) as usize }
+    fn opposite(&self, h: usize) -> usize { if h % 2 == 0 { self.dst[h / 2] } else { self.src[h / 2] } }
+}
+
+// The half-edges leaving each vertex, in a circular doubly linked list.
+struct Rotation {
+    first: Vec<Option<usize>>,
+    cw: Vec<usize>,
+    ccw: Vec<usize>,
+}
+
+impl Rotation {
+    fn new(n: usize, m: usize) -> Self {
+        Rotation {
+            first: repeat(n, None),
+            cw: repeat(2 * m, 0),
+            ccw: repeat(2 * m, 0),
+        }
+    }
+
+    fn insert_after(&mut self, v: usize, h: usize, reference: Option<usize>) {
+        match reference {
+            None => {
+                self.first[v] = Some(h);
+                self.cw[h] = h;
+                self.ccw[h] = h;
+            }
+            Some(r) => {
+                let next = self.cw[r];
+                self.cw[r] = h;
+                self.ccw[h] = r;
+                self.cw[h] = next;
+                self.ccw[next] = h;
+            }
+        }
+    }
+
+    fn insert_before(&mut self, v: usize, h: usize, reference: Option<usize>) {
+        match reference {
+            None => self.insert_after(v, h, None),
+            Some(r) => {
+                let prev = self.ccw[r];
+                self.cw[prev] = h;
+                self.ccw[h] = prev;
+                self.cw[h] = r;
+                self.ccw[r] = h;
+                if self.first[v] == Some(r) {
+                    self.first[v] = Some(h);
+                }
+            }
+        }
+    }
+
+    fn insert_first(&mut self, v: usize, h: usize) {
+        self.insert_before(v, h, self.first[v]);
+    }
+
+    fn around(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let first = self.first[v];
+        let mut next = first;
+        std::iter::from_fn(move || {
+            let h = next?;
+            next = Some(self.cw[h]).filter(|&x| Some(x) != first);
+            Some(h)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_planarity {
+    use std::fs::read_to_string;
+    use crate::algorithm::network_diversion::network_diversion;
+    use crate::structure::cost::Cost::{self, *};
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::simple_graph_strategy::SumWeights;
+    use crate::structure::weight::Weighted;
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize,usize)]) -> UndirectedGraph<f64, BasicEdge<f64>> {
+        let mut graph = UndirectedGraph::new(n);
+        for &(u,v) in edges {
+            graph.add_edge(BasicEdge::new(u, v, 1.0));
+        }
+        graph
+    }
+
+    fn complete(n: usize) -> UndirectedGraph<f64, BasicEdge<f64>> {
+        let edges: Vec<_> = (0..n).flat_map(|u| (u+1..n).map(move |v| (u,v))).collect();
+        graph(n, &edges)
+    }
+
+    fn bipartite(a: usize, b: usize) -> UndirectedGraph<f64, BasicEdge<f64>> {
+        let edges: Vec<_> = (0..a).flat_map(|u| (a..a+b).map(move |v| (u,v))).collect();
+        graph(a + b, &edges)
+    }
+
+    fn read(path: &str) -> UndirectedGraph<f64, BasicEdge<f64>> {
+        read_to_string(path).unwrap().parse().unwrap()
+    }
+
+    // The certificate must be non-planar, planar as soon as any edge is removed, and have the branch vertices of K5 or K3,3.
+    fn assert_kuratowski(n: usize, kuratowski: &[BasicEdge<f64>]) {
+        let edges: Vec<_> = kuratowski.iter().map(|e| (e.from(), e.to())).collect();
+        assert!(! is_planar(&graph(n, &edges)));
+        for i in 0..edges.len() {
+            let mut fewer = edges.clone();
+            fewer.remove(i);
+            assert!(is_planar(&graph(n, &fewer)), "The Kuratowski subgraph is not minimal, {:?} can be removed", edges[i]);
+        }
+
+        let mut degree = repeat(n, 0);
+        for &(u,v) in &edges {
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+        let branches: Vec<usize> = degree.into_iter().filter(|&d| d > 2).collect();
+        assert!(
+            branches == vec![4; 5] || branches == vec![3; 6],
+            "Expected the branch vertices of K5 or K3,3, but found the degrees {:?}", branches
+        );
+    }
+
+    fn assert_embeds(graph: &UndirectedGraph<f64, BasicEdge<f64>>) -> PlanarGraph<f64> {
+        let planar = match embed_planar::<SumWeights, _, _>(graph) {
+            Ok(planar) => planar,
+            Err(err) => panic!("Could not embed the graph: {}", err),
+        };
+        assert_eq!(planar.f() + planar.n(), planar.m() + 2);
+        planar
+    }
+
+    #[test]
+    fn test_small_graphs() {
+        assert!(is_planar(&complete(4)));
+        assert!(! is_planar(&complete(5)));
+        assert!(is_planar(&bipartite(2, 5)));
+        assert!(! is_planar(&bipartite(3, 3)));
+        assert!(is_planar(&graph(0, &[])));
+        assert!(is_planar(&graph(3, &[(0, 0), (0, 1), (1, 0)])));
+        for n in 2..=4 {
+            assert_embeds(&complete(n));
+        }
+        assert_embeds(&bipartite(2, 5));
+    }
+
+    #[test]
+    fn test_kuratowski() {
+        for (n, graph) in [(5, complete(5)), (6, bipartite(3, 3)), (7, complete(7)), (8, bipartite(4, 4))] {
+            match planarity(&graph) {
+                Planar {..} => panic!("K{} was embedded in the plane", n),
+                NonPlanar { kuratowski } => assert_kuratowski(n, &kuratowski),
+            }
+        }
+    }
+
+    #[test]
+    fn test_kuratowski_in_larger_graph() {
+        // A grid is planar, but not with two crossing chords between its corners.
+        let mut edges = Vec::new();
+        for r in 0..6 {
+            for c in 0..6 {
+                if c < 5 { edges.push((6*r + c, 6*r + c + 1)); }
+                if r < 5 { edges.push((6*r + c, 6*r + c + 6)); }
+            }
+        }
+        assert!(is_planar(&graph(36, &edges)));
+        edges.extend([(0, 35), (5, 30), (2, 33)]);
+        let petersen = read("data/special_graphs/petersen/petersen.in");
+        for (n, graph) in [(36, graph(36, &edges)), (petersen.n(), petersen)] {
+            match planarity(&graph) {
+                Planar {..} => panic!("A non-planar graph was embedded in the plane"),
+                NonPlanar { kuratowski } => assert_kuratowski(n, &kuratowski),
+            }
+        }
+    }
+
+    #[test]
+    fn test_embed_planar_graphs() {
+        for name in ["grid5", "gamma4"] {
+            assert_embeds(&read(&["data/special_graphs/", name, "/", name, ".in"].concat()));
+        }
+        for i in 1..=5 {
+            let path = format!("data/planar_graphs/small_planar_graphs/small_planar{}/small_planar{}.in", i, i);
+            let drawn: PlanarGraph<f64> = read_to_string(&path).unwrap().parse().unwrap();
+            let mut plain = UndirectedGraph::new(drawn.n());
+            for e in drawn.real().edges().into_iter().filter(|e| e.from() < e.to()) {
+                plain.add_edge(BasicEdge::new(e.from(), e.to(), e.weight()));
+            }
+
+            let embedded = assert_embeds(&plain);
+            assert_eq!(embedded.f(), drawn.f());
+            let queries = read_to_string(format!("data/planar_graphs/small_planar_graphs/small_planar{}/small_planar{}.diversion", i, i)).unwrap();
+            for query in queries.lines() {
+                let words: Vec<&str> = query.split_whitespace().collect();
+                if words.len() < 5 {
+                    continue;
+                }
+                let [s, t, du, dv] = [0, 1, 2, 3].map(|j| words[j].parse().unwrap());
+                let expected = Cost::from(words[4].parse::<f64>());
+                let actual = network_diversion(&embedded, s, t, (du, dv)).unwrap().cost();
+                let correct = match (expected, actual) {
+                    (Finite(expected), Some(actual)) => (expected - actual).abs() < 1e-9,
+                    (Infinite, None) => true,
+                    _ => false,
+                };
+                assert!(correct, "small_planar{}: {}, but found {:?}", i, query, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_planar_error() {
+        match embed_planar::<SumWeights, _, _>(&bipartite(3, 3)) {
+            Err(NotPlanar { kuratowski }) => assert_eq!(kuratowski.len(), 9),
+            _ => panic!("K3,3 was embedded in the plane"),
+        }
+    }
+}
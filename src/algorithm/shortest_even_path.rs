@@ -0,0 +1,34 @@
+use crate::algorithm::shortest_odd_path::shortest_odd_path;
+use crate::algorithm::utility::attach_pendant;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::path_result::{PathResult, PathResult::*};
+use crate::structure::weight::Weight;
+
+/**
+Problem: Shortest Even Path
+In: an undirected graph G, two vertices s,t in V(G)
+Out: an s-t-path in G of minimum cost using an even, non-zero number of edges, if one exists.
+
+An even s-t-path in G is an odd s-p-path in G + tp, where p is a new vertex only adjacent to t by an edge of cost 0.
+*/
+pub fn shortest_even_path<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> PathResult<W,E> {
+    if s == t {
+        return Impossible;
+    }
+    if let Some((pendant, p)) = attach_pendant(graph, t) {
+        match shortest_odd_path(&pendant, s, p) {
+            Impossible => Impossible,
+            Possible {cost, mut path} => {
+                path.pop();
+                Possible {
+                    cost,
+                    path,
+                }
+            }
+        }
+    }
+    else {
+        Impossible
+    }
+}
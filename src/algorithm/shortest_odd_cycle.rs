@@ -0,0 +1,147 @@
+use crate::algorithm::shortest_odd_path::shortest_odd_path;
+use crate::structure::error::{Error, Error::*};
+use crate::structure::graph::csr_graph::CsrGraph;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::path_result::{PathResult, PathResult::*};
+use crate::structure::weight::Weight;
+
+/**
+Problem: Shortest Odd Cycle through a vertex
+In: an undirected graph G, and a vertex v in V(G)
+Out: a cycle in G of minimum cost through v using an odd number of edges, if one exists.
+
+An odd cycle through v is an odd path from v to a copy of v that has all the same neighbours.
+The cycle starts and ends in v. A loop counts as an odd cycle of a single edge.
+
+Fails if v is not in the graph.
+*/
+pub fn shortest_odd_cycle_through_vertex<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, v: usize) -> Result<PathResult<W,E>, Error> {
+    if v >= graph.n() {
+        return Err(VertexOutOfRange {vertex: v, n: graph.n()});
+    }
+    Ok(cycle_through_vertex(graph, v, 0))
+}
+
+/**
+Problem: Shortest Odd Cycle through an edge
+In: an undirected graph G, and an edge (u,v) in E(G)
+Out: a cycle in G of minimum cost through (u,v) using an odd number of edges, if one exists.
+
+Without the edge, the rest of such a cycle is an even v-u-path, which is an odd path from v to a new vertex p only adjacent to u.
+The edge from u to p costs as much as (u,v), so the cost of the path is the cost of the whole cycle.
+The cycle starts with (u,v), and ends in u. If there are several edges between u and v, the cheapest one is used.
+
+Fails if (u,v) is not in the graph.
+*/
+pub fn shortest_odd_cycle_through_edge<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, (u, v): (usize, usize)) -> Result<PathResult<W,E>, Error> {
+    for vertex in [u, v] {
+        if vertex >= graph.n() {
+            return Err(VertexOutOfRange {vertex, n: graph.n()});
+        }
+    }
+    let edge = cheapest(graph.neighbours(u).filter(|e| e.to() == v))
+        .ok_or(MissingEdge {from: u, to: v})?;
+    if u == v {
+        return Ok(Possible {
+            cost: edge.weight(),
+            path: vec![edge],
+        });
+    }
+
+    // Other edges between u and v can never be part of an even v-u-path, so we remove all of them.
+    let p = graph.n();
+    let (link, _) = edge.subdivide(p);
+    let between = |e: &E| (e.from(), e.to()) == (u, v) || (e.from(), e.to()) == (v, u);
+    let neighbourhoods = graph.vertices()
+        .map(|w| {
+            let mut neighbourhood: Vec<E> = graph.neighbours(w).filter(|e| ! between(e)).collect();
+            if w == u {
+                neighbourhood.push(link.clone());
+            }
+            neighbourhood
+        })
+        .chain([vec![link.reverse()]]);
+    let pendant: CsrGraph<W,E> = CsrGraph::from_neighbourhoods(neighbourhoods);
+
+    Ok(match shortest_odd_path(&pendant, v, p) {
+        Impossible => Impossible,
+        Possible {cost, mut path} => {
+            path.pop();
+            path.insert(0, edge);
+            Possible {
+                cost,
+                path,
+            }
+        }
+    })
+}
+
+/**
+Problem: Shortest Odd Cycle
+In: an undirected graph G
+Out: a cycle in G of minimum cost using an odd number of edges, if one exists.
+
+This is the cheapest of the shortest odd cycles through each vertex.
+Every cycle through a vertex has already been considered once we are done with it, so it is left out of the later searches.
+*/
+pub fn shortest_odd_cycle<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> PathResult<W,E> {
+    graph.vertices()
+        .map(|v| cycle_through_vertex(graph, v, v))
+        .fold(Impossible, cheaper)
+}
+
+// The shortest odd cycle through v, ignoring every vertex before the first one.
+fn cycle_through_vertex<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, v: usize, first: usize) -> PathResult<W,E> {
+    let copy = graph.n();
+    let kept = |e: &E| e.from() >= first && e.to() >= first;
+    let neighbourhoods = graph.vertices()
+        .map(|u| {
+            let mut neighbourhood: Vec<E> = graph.neighbours(u).filter(kept).collect();
+            if u != v {
+                let into_copy: Vec<E> = neighbourhood.iter()
+                    .filter(|e| e.to() == v)
+                    .map(|e| e.subdivide(copy).0)
+                    .collect();
+                neighbourhood.extend(into_copy);
+            }
+            neighbourhood
+        })
+        .chain([
+            graph.neighbours(v)
+                .filter(|e| kept(e) && e.to() != v)
+                .map(|e| e.reverse().subdivide(copy).0.reverse())
+                .collect()
+        ]);
+    let doubled: CsrGraph<W,E> = CsrGraph::from_neighbourhoods(neighbourhoods);
+
+    let cycle = match shortest_odd_path(&doubled, v, copy) {
+        Impossible => Impossible,
+        Possible {cost, path} => Possible {
+            cost,
+            path: path.into_iter()
+                .map(|e| if e.to() == copy { e.subdivide(v).0 } else { e })
+                .collect(),
+        },
+    };
+    let looped = match cheapest(graph.neighbours(v).filter(|e| e.to() == v)) {
+        Some(e) if v >= first => Possible {
+            cost: e.weight(),
+            path: vec![e],
+        },
+        _ => Impossible,
+    };
+    cheaper(cycle, looped)
+}
+
+fn cheapest<W: Weight, E: Edge<W>>(edges: impl Iterator<Item = E>) -> Option<E> {
+    edges.reduce(|a, b| if b.weight() < a.weight() { b } else { a })
+}
+
+fn cheaper<W: Weight, E: Edge<W>>(a: PathResult<W,E>, b: PathResult<W,E>) -> PathResult<W,E> {
+    match (&a, &b) {
+        (Impossible, _) => b,
+        (Possible {cost: x, ..}, Possible {cost: y, ..}) if y < x => b,
+        _ => a,
+    }
+}
@@ -0,0 +1,59 @@
+use crate::algorithm::shortest_odd_path::shortest_odd_path;
+use crate::structure::graph::csr_graph::CsrGraph;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::path_result::{PathResult, PathResult::*};
+use crate::structure::weight::Weight;
+use crate::utility::misc::repeat;
+
+/**
+Problem: Shortest Odd Labelled Path
+In: an undirected graph G where every edge is labelled with 0 or 1, two vertices s and t
+Out: an s-t-path in G of minimum cost whose labels sum to 1 (mod 2), if one exists
+
+Only the edges with label 1 count towards the parity, so this is the same as shortest_odd_path when every edge is labelled.
+Every edge labelled 0 is subdivided with a new vertex in the middle, which turns it into two edges, so that it does not change the parity.
+The first half has the original cost and the second half costs nothing.
+The new vertices only have two neighbours each, so every path in the subdivided graph is also a path in G.
+*/
+pub fn shortest_odd_labelled_path<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> PathResult<W,E> {
+    let n = graph.n();
+    let mut neighbourhoods: Vec<Vec<E>> = repeat(n, Vec::new());
+    let mut middles: Vec<E> = Vec::new();
+    for u in graph.vertices() {
+        // Loops can never be part of a path, and every other edge is added once from each end.
+        for e in graph.neighbours(u).filter(|e| u < e.to()) {
+            if e.label() {
+                neighbourhoods[u].push(e.clone());
+                neighbourhoods[e.to()].push(e.reverse());
+            }
+            else {
+                let (first, second) = e.subdivide(n + middles.len());
+                neighbourhoods[u].push(first.clone());
+                neighbourhoods[e.to()].push(second.reverse());
+                neighbourhoods.push(vec![first.reverse(), second]);
+                middles.push(e);
+            }
+        }
+    }
+    let subdivided: CsrGraph<W,E> = CsrGraph::from_neighbourhoods(neighbourhoods);
+
+    match shortest_odd_path(&subdivided, s, t) {
+        Impossible => Impossible,
+        Possible {cost, path} => Possible {
+            cost,
+            path: path.into_iter()
+                .filter(|e| e.from() < n)
+                .map(|e| {
+                    if e.to() < n {
+                        e
+                    }
+                    else {
+                        let original = &middles[e.to() - n];
+                        if original.from() == e.from() { original.clone() } else { original.reverse() }
+                    }
+                })
+                .collect(),
+        },
+    }
+}
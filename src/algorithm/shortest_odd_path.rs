@@ -1,27 +1,50 @@
 use std::cmp::Reverse;
 use crate::structure::path_result::{PathResult, PathResult::*};
 use crate::structure::cost::{Cost::*, Cost};
-use crate::structure::graph::undirected_graph::UndirectedGraph;
-use crate::utility::misc::{debug, repeat};
+use crate::structure::graph::csr_graph::CsrGraph;
+use crate::structure::graph::Graph;
+use crate::utility::misc::repeat;
+use crate::utility::logging::{debug, trace};
 use std::collections::{BinaryHeap};
 use crate::algorithm::utility;
 use crate::structure::basis::{Basis, UnionFindBase};
 use crate::structure::graph::edge::{Edge};
 use crate::structure::todo::{Todo, Todo::*};
-use crate::structure::weight::{Weight};
-
-pub struct DerigsAlgorithm<W: Weight, E: Edge<W>> {
-    graph: UndirectedGraph<W,E>,
-    d_plus: Vec<Cost<W>>,
-    d_minus: Vec<Cost<W>>,
-    pred: Vec<Option<E>>,
-    basis: UnionFindBase,
+use crate::structure::weight::{Overflowing, Weight};
+use crate::algorithm::bipartite::bipartition;
+use crate::algorithm::shortest_path::bfs;
+use crate::structure::bipartition::Bipartition::Bipartite;
+use crate::structure::infeasibility::{Infeasibility, Infeasibility::*};
+use queues::{IsQueue, Queue};
+use crate::structure::event::{Event, Event::{Start, Scan, DPlus, DMinus, SetBase, Done}, Observer};
+use crate::structure::error::{Error, Error::*};
+use crate::structure::stamped::Stamped;
+
+pub struct DerigsAlgorithm<W, E, B = UnionFindBase, O = ()>
+    where W: Weight,
+          E: Edge<W>,
+          B: Basis,
+          O: Observer<W,E>,
+{
+    graph: CsrGraph<W,E>,
+    d_plus: Stamped<Cost<W>>,
+    d_minus: Stamped<Cost<W>>,
+    pred: Stamped<Option<E>>,
+    // The edge closing the blossom that gave a vertex its d_plus, if it did not get it through its mirror.
+    blossom_pred: Stamped<Option<E>>,
+    basis: B,
     s: usize,
     t: usize,
     orig_n: usize,
-    completed: Vec<bool>,
+    completed: Stamped<bool>,
     pq: BinaryHeap<Reverse<Todo<W,E>>>,
     in_current_blossom: Vec<bool>,
+    observer: O,
+    // Set as soon as a cost grows too large for W, which ends the search.
+    overflow: bool,
+    // Every sink that was reached before its mirror, when searching for all sinks at once.
+    // Nothing on the way to it changes after that, so its path can be backtracked once the search is done.
+    answered: Vec<bool>,
 }
 
 /**
@@ -30,81 +53,429 @@ In: an undirected graph G, two vertices s,t in V(G)
 Out: an s-t-path in G of minimum cost using an odd number of edges, if one exists.
 */
 
-pub fn shortest_odd_path<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize, t: usize) -> PathResult<W,E> {
-    DerigsAlgorithm::init(graph, s, t).solve()
+pub fn shortest_odd_path<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> PathResult<W,E> {
+    DerigsAlgorithm::<W, E>::new(graph, ()).query(s, t).expect(OVERFLOW)
 }
 
-impl <W: Weight, E: Edge<W>> DerigsAlgorithm<W, E> {
-    fn init(graph: &UndirectedGraph<W,E>, s: usize, t: usize) -> Self where Self: Sized {
-        let mirror_graph = utility::create_mirror_graph(graph, s, t);
-        let n = mirror_graph.n();
+/**
+The same as shortest_odd_path, but fails instead of panicking if s or t are not in the graph, or if a cost grows too large for W.
+Only weights that notice when they overflow can fail the latter way, such as Checked.
+Closing a blossom adds up the costs of two paths, so the search can also fail if an odd cycle on the way costs more than W can hold.
+*/
+pub fn try_shortest_odd_path<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> Result<PathResult<W,E>, Error> {
+    for vertex in [s, t] {
+        if vertex >= graph.n() {
+            return Err(VertexOutOfRange {vertex, n: graph.n()});
+        }
+    }
+    DerigsAlgorithm::<W, E>::new(graph, ()).query(s, t)
+}
+
+const OVERFLOW: &str = "The cost of a path overflowed, use try_shortest_odd_path to handle it";
+
+/**
+The same as shortest_odd_path, but with another implementation of Basis to keep track of the blossoms.
+*/
+pub fn shortest_odd_path_with_basis<B, W, E, G>(graph: &G, s: usize, t: usize) -> PathResult<W,E>
+    where B: Basis,
+          W: Weight,
+          E: Edge<W>,
+          G: Graph<W,E>,
+{
+    DerigsAlgorithm::<W, E, B>::new(graph, ()).query(s, t).expect(OVERFLOW)
+}
+
+/**
+The same as shortest_odd_path, but every step of the search is reported to the observer as it happens.
+Pass a mutable reference to keep the observer afterwards, such as a Recorder to replay the search later.
+*/
+pub fn shortest_odd_path_observed<W, E, G, O>(graph: &G, s: usize, t: usize, observer: O) -> PathResult<W,E>
+    where W: Weight,
+          E: Edge<W>,
+          G: Graph<W,E>,
+          O: Observer<W,E>,
+{
+    DerigsAlgorithm::<W, E, UnionFindBase, O>::new(graph, observer).query(s, t).expect(OVERFLOW)
+}
+
+/**
+Answers shortest_odd_path for many pairs of vertices in the same graph.
+The mirror graph and the state of the search are built once, and every query starts by resetting the state in constant time.
+*/
+pub struct OddPathSolver<W: Weight, E: Edge<W>> {
+    algorithm: DerigsAlgorithm<W,E>,
+}
+
+impl <W: Weight, E: Edge<W>> OddPathSolver<W,E> {
+    pub fn new<G: Graph<W,E>>(graph: &G) -> Self {
+        OddPathSolver {
+            algorithm: DerigsAlgorithm::new(graph, ()),
+        }
+    }
+
+    pub fn query(&mut self, s: usize, t: usize) -> PathResult<W,E> {
+        self.algorithm.query(s, t).expect(OVERFLOW)
+    }
+
+    // The same as query, but fails like try_shortest_odd_path does.
+    pub fn try_query(&mut self, s: usize, t: usize) -> Result<PathResult<W,E>, Error> {
+        for vertex in [s, t] {
+            if vertex >= self.algorithm.orig_n {
+                return Err(VertexOutOfRange {vertex, n: self.algorithm.orig_n});
+            }
+        }
+        self.algorithm.query(s, t)
+    }
+}
+
+/**
+Problem: Single Source Shortest Odd Paths
+In: an undirected graph G, a source vertex s in V(G)
+Out: the cost of the shortest odd s-t-path for every t in V(G), and a function that extracts such a path for a given t.
+
+Runs one search towards every sink at once, with only the mirror of s left out of the mirror graph.
+Until it reaches either t or the mirror of t, it does exactly what the search for an odd s-t-path does,
+so if t comes first, the shortest odd path to t is known right then.
+If the mirror of t comes first instead, because an even path to t is just as cheap or cheaper, the search goes on through t in a way
+an odd s-t-path can not, so t gets a search of its own afterwards. That is only needed if some odd s-t-path exists at all,
+which the blocks between s and t tell for every t at once, so a bipartite graph never needs more than the one search.
+The paths are only backtracked when they are asked for.
+*/
+pub fn shortest_odd_paths_from<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> (Vec<Cost<W>>, impl Fn(usize) -> PathResult<W,E>) {
+    let (dist, path_to, _) = odd_paths_from(graph, s);
+    (dist, path_to)
+}
+
+// The same as shortest_odd_paths_from, but also tells how many searches it took.
+fn odd_paths_from<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> (Vec<Cost<W>>, impl Fn(usize) -> PathResult<W,E>, usize) {
+    let mut search = DerigsAlgorithm::<W, E>::new(graph, ());
+    let answered = search.query_all(s).expect(OVERFLOW);
+    let possible = has_odd_path_from(graph, s);
+
+    let mut solver = None;
+    let mut searches = 1;
+    let mut others = repeat(graph.n(), None);
+    let mut dist = Vec::with_capacity(graph.n());
+    for t in graph.vertices() {
+        dist.push(if answered[t] {
+            search.d_minus[t].clone()
+        }
+        else if possible[t] {
+            debug!("The mirror of {} was reached before {} itself, so it needs a search of its own.", t, t);
+            let result = solver.get_or_insert_with(|| DerigsAlgorithm::<W, E>::new(graph, ())).query(s, t).expect(OVERFLOW);
+            searches += 1;
+            let cost = match &result {
+                Possible {cost, ..} => Finite(cost.clone()),
+                Impossible => Infinite,
+            };
+            others[t] = Some(result);
+            cost
+        }
+        else {
+            Infinite
+        });
+    }
+    let path_to = move |t: usize| {
+        if answered[t] {
+            search.backtrack_path(t)
+        }
+        else {
+            others[t].clone().unwrap_or(Impossible)
+        }
+    };
+    (dist, path_to, searches)
+}
+
+/**
+Problem: Odd Path Infeasibility
+In: an undirected graph G, two vertices s,t in V(G)
+Out: a certificate that G has no odd s-t-path, or nothing if it has one.
 
-        debug(format!("\n\nLooking for an odd {}-{}-path here:\n{:?}\n", s, t, mirror_graph));
+Every s-t-path walks through the same chain of blocks between s and t in the block-cut tree, and never leaves them.
+A block that is not bipartite has both an odd and an even path between any two of its vertices, so then some s-t-path is odd.
+Otherwise the chain is bipartite as a whole, and every s-t-path is odd if s and t get different colours, and even if they get the same.
+*/
+pub fn explain_impossible<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> Option<Infeasibility> {
+    let dist = bfs(graph, s);
+    if dist[t].is_infinite() {
+        return Some(Disconnected {
+            component: graph.vertices().filter(|&u| dist[u].is_finite()).collect(),
+        });
+    }
 
-        let mut d_plus= repeat(n, Infinite);
-        let mut d_minus = repeat(n, Infinite);
-        let mut pred = repeat(n, None);
-        let mut completed = repeat(n, false);
-        let mut pq = BinaryHeap::new();
-        d_plus[s] = Finite(0.into());
+    let n = graph.n();
+    let mut within = repeat(n, false);
+    within[s] = true;
+    if s != t {
+        let (blocks, parent, _) = block_cut_tree(graph, s);
+        let mut x = t;
+        while x != s {
+            if x >= n {
+                blocks[x - n].iter().for_each(|&u| within[u] = true);
+            }
+            x = parent[x];
+        }
+    }
+
+    let chain: CsrGraph<W,E> = CsrGraph::from_neighbourhoods(graph.vertices().map(|u| {
+        graph.neighbours(u)
+            .filter(|e| within[e.from()] && within[e.to()] && e.from() != e.to())
+            .collect::<Vec<E>>()
+    }));
+    match bipartition(&chain) {
+        Bipartite {colouring} if colouring[s] == colouring[t] => {
+            debug!("Every {}-{}-path is even, because they all stay within a bipartite chain of blocks.", s, t);
+            Some(EvenBlocks {
+                colouring: graph.vertices().map(|u| Some(colouring[u]).filter(|_| within[u])).collect(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/*
+The block-cut tree of the component of s, rooted in s, where block i is the node n+i, adjacent to all of its vertices.
+Returns the blocks, the parent of every node in the tree, which is usize::MAX outside of it, and the nodes in breadth first order.
+*/
+fn block_cut_tree<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> (Vec<Vec<usize>>, Vec<usize>, Vec<usize>) {
+    let n = graph.n();
+    let blocks = utility::blocks(graph, s);
+    let mut tree: Vec<Vec<usize>> = repeat(n + blocks.len(), Vec::new());
+    for (i, block) in blocks.iter().enumerate() {
+        for &u in block {
+            tree[u].push(n + i);
+            tree[n + i].push(u);
+        }
+    }
+    let mut parent = repeat(tree.len(), usize::MAX);
+    let mut order = Vec::new();
+    let mut q: Queue<usize> = Queue::new();
+    parent[s] = s;
+    q.add(s).unwrap();
+    while let Ok(x) = q.remove() {
+        order.push(x);
+        for &y in &tree[x] {
+            if parent[y] == usize::MAX {
+                parent[y] = x;
+                q.add(y).unwrap();
+            }
+        }
+    }
+    (blocks, parent, order)
+}
 
-        for e in &mirror_graph[&s] {
-            pq.push(Reverse(Vertex(e.weight(), e.to())));
-            d_minus[e.to()] = Finite(e.weight());
-            pred[e.to()] = Some(e.clone());
+/*
+Whether there is an odd s-t-path for every t, by the same argument as explain_impossible, but for all of them at once.
+Any spanning tree colours the vertices so that a block is bipartite if and only if none of its edges join two vertices of the same colour,
+since the tree path between two vertices of a block never leaves it. The colours come from the breadth first search from s.
+*/
+fn has_odd_path_from<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> Vec<bool> {
+    let n = graph.n();
+    let dist = bfs(graph, s);
+    let colour: Vec<Option<bool>> = dist.iter()
+        .map(|d| match d {
+            Finite(d) => Some(d % 2 == 1),
+            Infinite => None,
+        })
+        .collect();
+    let (blocks, parent, order) = block_cut_tree(graph, s);
+
+    // An edge belongs to the block of the endpoint that is further from s in the tree, unless the other one is below the same block.
+    let mut bipartite = repeat(blocks.len(), true);
+    for u in graph.vertices().filter(|&u| u != s && colour[u].is_some()) {
+        for v in graph.neighbours(u).map(|e| e.to()).filter(|&v| v != u && colour[v] == colour[u]) {
+            let block = if v != s && parent[parent[v]] == u { parent[v] } else { parent[u] };
+            bipartite[block - n] = false;
         }
-        completed[s] = true;
-        completed[s + graph.n()] = true;
+    }
+
+    // Whether any block on the way from s to each node in the tree is not bipartite.
+    let mut odd_block = repeat(parent.len(), false);
+    for &x in order.iter().skip(1) {
+        odd_block[x] = odd_block[parent[x]] || (x >= n && ! bipartite[x - n]);
+    }
+    graph.vertices()
+        .map(|t| t != s && colour[t].is_some() && (odd_block[t] || colour[t] != colour[s]))
+        .collect()
+}
+
+impl <W, E, B, O> DerigsAlgorithm<W, E, B, O>
+    where W: Weight,
+          E: Edge<W>,
+          B: Basis,
+          O: Observer<W,E>,
+{
+    fn new<G: Graph<W,E>>(graph: &G, observer: O) -> Self where Self: Sized {
+        let mirror_graph = utility::create_mirror_graph(graph);
+        let n = mirror_graph.n();
 
         DerigsAlgorithm {
             graph: mirror_graph,
-            d_plus,
-            d_minus,
-            pred,
-            basis: UnionFindBase::new(n),
-            s,
-            t,
+            d_plus: Stamped::new(n, Infinite),
+            d_minus: Stamped::new(n, Infinite),
+            pred: Stamped::new(n, None),
+            blossom_pred: Stamped::new(n, None),
+            basis: B::new(n),
+            s: 0,
+            t: 0,
             orig_n: graph.n(),
-            completed,
-            pq,
+            completed: Stamped::new(n, false),
+            pq: BinaryHeap::new(),
             in_current_blossom: repeat(n, false),
+            observer,
+            overflow: false,
+            answered: Vec::new(),
         }
     }
 
-    fn solve(&mut self) -> PathResult<W,E> {
+    // Search towards every sink at once, and tell which of them were reached before their mirrors.
+    fn query_all(&mut self, s: usize) -> Result<Vec<bool>, Error> {
+        // Using s as the sink as well makes sure the search never stops early, since s is completed from the start.
+        self.init(s, s);
+        self.answered = repeat(self.orig_n, false);
+        while ! self.control() {}
+        if self.overflow {
+            return Err(Overflow);
+        }
+        Ok(std::mem::take(&mut self.answered))
+    }
+
+    fn query(&mut self, s: usize, t: usize) -> Result<PathResult<W,E>, Error> {
+        self.init(s, t);
+        self.solve()
+    }
+
+    // Forget everything from the previous search, and start a new one from s.
+    fn init(&mut self, s: usize, t: usize) {
+        self.d_plus.reset();
+        self.d_minus.reset();
+        self.pred.reset();
+        self.blossom_pred.reset();
+        self.completed.reset();
+        self.basis.reset();
+        self.pq.clear();
+        self.overflow = false;
+        self.s = s;
+        self.t = t;
+
+        trace!("Looking for an odd {}-{}-path here:\n{:?}", s, t, self.graph);
+
+        self.d_plus[s] = Finite(W::zero());
+        self.observer.observe(Start {n: self.orig_n, s, t});
+        self.observer.observe(DPlus {vertex: s, value: self.d_plus[s].clone()});
+
+        for e in &self.graph[&s] {
+            // Keep the cheapest of any parallel edges out of s.
+            if Finite(e.weight()) < self.d_minus[e.to()] {
+                self.pq.push(Reverse(Vertex(e.weight(), e.to())));
+                self.d_minus[e.to()] = Finite(e.weight());
+                self.pred[e.to()] = Some(e.clone());
+                self.observer.observe(DMinus {vertex: e.to(), value: self.d_minus[e.to()].clone(), pred: e.clone()});
+            }
+        }
+        self.completed[s] = true;
+        self.completed[s + self.orig_n] = true;
+    }
+
+    fn solve(&mut self) -> Result<PathResult<W,E>, Error> {
         if self.s == self.t {
-            return Impossible;
+            self.observer.observe(Done {cost: Infinite});
+            return Ok(Impossible);
         }
 
         while ! self.control() {}
 
+        if self.overflow {
+            debug!("Gave up the search for an odd {}-{}-path, the costs grew too large.", self.s, self.t);
+            return Err(Overflow);
+        }
+
         if self.d_minus[self.t].is_infinite() {
-            debug(format!("\n\nWe can now definitely conclude that no odd {}-{}-path exists.\n\n", self.s, self.t));
-            return Impossible;
+            debug!("We can now definitely conclude that no odd {}-{}-path exists.", self.s, self.t);
+            self.observer.observe(Done {cost: Infinite});
+            return Ok(Impossible);
         }
 
-        let mut curr = self.pred[self.t].clone().unwrap();
-        let mut cost = curr.weight();
-        let mut path = vec![curr.clone()];
-        while curr.from() != self.s {
-            curr = self.pred[self.mirror(curr.from())].clone().unwrap();
-            cost = cost + curr.weight();
-            if curr.from() < self.orig_n {
-                path.push(curr.clone());
-            }
-            else {
-                path.push(curr.shift_by(-(self.orig_n as i64)))
-            }
+        let result = self.backtrack_path(self.t);
+        if let Possible {cost, path} = &result {
+            debug!("Path of cost {} is possible: {:?}", cost, path);
+            self.observer.observe(Done {cost: Finite(cost.clone())});
         }
+        Ok(result)
+    }
+
+    fn backtrack_path(&self, t: usize) -> PathResult<W,E> {
+        if t == self.s || self.d_minus[t].is_infinite() {
+            return Impossible;
+        }
+        let last = self.pred[t].clone().unwrap();
+        let mut path = Vec::new();
+        self.backtrack_plus(last.from(), usize::MAX, &mut path);
         path.reverse();
-        debug(format!("Path of cost {} is possible: {:?\n\n}",cost, path));
+        path.push(last);
+        let cost = path.iter().fold(W::zero(), |cost: W, e| cost + e.weight());
         Possible {
             cost,
-            path,
+            path: path.into_iter()
+                .map(|e| if e.from() < self.orig_n { e } else { e.shift_by(-(self.orig_n as i64)) })
+                .collect(),
+        }
+    }
+
+    /*
+    Push the edges of the even path from s to u onto the path, from u and backwards, until we push the edge out of stop.
+    Returns true if we stopped there, or false if we went all the way back to s.
+
+    A vertex that got its d_plus from a blossom edge (x, y) is reached by going from s to x, over to y, and then along the
+    even path to y backwards until we reach the mirror of u. That part of the path is found by backtracking from y first.
+    Blossoms can be nested as deep as the graph is large, so the ones we are unfolding are kept on a stack of our own.
+    */
+    fn backtrack_plus(&self, mut u: usize, mut stop: usize, path: &mut Vec<E>) -> bool {
+        // Where the part of the path inside each blossom starts, the stop to go back to afterwards, and the edge that closed it.
+        let mut unfolding: Vec<(usize, usize, E)> = Vec::new();
+        loop {
+            while u != stop && u != self.s {
+                match &self.blossom_pred[u] {
+                    None => {
+                        let e = self.pred[self.mirror(u)].clone().unwrap();
+                        u = e.from();
+                        path.push(e);
+                    }
+                    Some(e) => {
+                        unfolding.push((path.len(), stop, e.clone()));
+                        stop = self.mirror(u);
+                        u = e.to();
+                    }
+                }
+            }
+
+            let mut found = u == stop;
+            loop {
+                let Some((start, outer, e)) = unfolding.pop() else { return found };
+                debug_assert!(found, "The even path to {} does not go through {}", e.to(), stop);
+                path[start..].reverse();
+                for f in &mut path[start..] {
+                    *f = f.reverse();
+                }
+                stop = outer;
+                // If the way back through the blossom passes the outer stop, the outer part is done as well.
+                if let Some(i) = path[start..].iter().position(|f| f.from() == stop) {
+                    path.truncate(start + i + 1);
+                    found = true;
+                    continue;
+                }
+                u = e.from();
+                path.push(e);
+                break;
+            }
         }
     }
+
     // Return true if the search is done. Either because we found the shortest odd s-t-path, or because none exist.
     fn control(&mut self) -> bool {
+        if self.overflow {
+            return true;
+        }
         while let Some(Reverse(todo)) = self.pq.peek() {
             match todo {
                 Vertex(_,u) => if self.completed[*u] { self.pq.pop(); } else { break; }
@@ -116,11 +487,17 @@ impl <W: Weight, E: Edge<W>> DerigsAlgorithm<W, E> {
             None => return true, // No odd path exists :(
             Some(Reverse(Vertex(_, u))) => {
                 if u == self.t { return true; } // Shortest odd path has been found :)
+                if u < self.answered.len() {
+                    self.answered[u] = true;
+                }
+                trace!("Scanning {} with d_minus = {:?}", u, self.d_minus[u]);
                 let m = self.mirror(u);
-                self.d_plus[m] = self.d_minus[u];
+                self.d_plus[m] = self.d_minus[u].clone();
+                self.observer.observe(DPlus {vertex: m, value: self.d_plus[m].clone()});
                 self.scan(m);
             }
             Some(Reverse(Blossom(_,e))) => {
+                trace!("Forming a blossom from {:?}", e);
                 self.blossom(&e);
             }
         }
@@ -130,140 +507,179 @@ impl <W: Weight, E: Edge<W>> DerigsAlgorithm<W, E> {
 
     fn scan(&mut self, u: usize) {
         self.completed[u] = true;
+        self.observer.observe(Scan {vertex: u});
         let dist_u = self.d_plus[u].expect(format!("        We called self.scan({}), but self.d_plus[{}] is undefined!", u, u).as_str());
         for e in &self.graph[&u] {
             let w = e.weight();
             let v = e.to();
-            let new_dist_v = dist_u + w;
+            // The mirrors of s and t are left out of the search, so that the path can only visit s and t once.
+            if v == self.s + self.orig_n || v == self.t + self.orig_n {
+                continue;
+            }
+            let new_dist_v = dist_u.clone() + w.clone();
             if ! self.completed[v] {
-                if Finite(new_dist_v) >= self.d_minus[v] { continue }
-                
-                self.d_minus[v] = Finite(new_dist_v);
+                if Finite(new_dist_v.clone()) >= self.d_minus[v] { continue }
+                if new_dist_v.is_overflow() {
+                    self.overflow = true;
+                    return;
+                }
+
+                self.d_minus[v] = Finite(new_dist_v.clone());
                 self.pred[v] = Some(e.clone());
                 self.pq.push(Reverse(Vertex(new_dist_v, v)));
+                self.observer.observe(DMinus {vertex: v, value: self.d_minus[v].clone(), pred: e.clone()});
             }
 
-            else if let (Finite(dist_v), true) = (self.d_plus[v], ! self.basis.same_base(u, v)) {
-                self.pq.push(Reverse(Blossom(dist_u + dist_v + w, e.clone())));
-                if Finite(new_dist_v) < self.d_minus[v] {
-                    self.d_minus[v] = Finite(new_dist_v);
-                    self.pred[v] = Some(e.clone());
+            else if let (Finite(dist_v), true) = (&self.d_plus[v], ! self.basis.same_base(u, v)) {
+                // v can only be reached again by going around a blossom, which gets its own path once it is formed.
+                let value = dist_u.clone() + dist_v.clone() + w;
+                if value.is_overflow() {
+                    self.overflow = true;
+                    return;
                 }
+                self.pq.push(Reverse(Blossom(value, e.clone())));
             }
         }
     }
 
     fn blossom(&mut self, e: &E) {
-        let (b, p1, p2) = self.backtrack_blossom(e);
-
-        let s1 = self.set_blossom_values(&p1);
-        let s2 = self.set_blossom_values(&p2);
-
-        self.set_edge_bases(b, &p1);
-        self.set_edge_bases(b, &p2);
+        let (b, bases_to, bases_from) = self.backtrack_blossom(e);
+        trace!("The blossom has base {}, and the outer bases {:?} and {:?}", b, bases_to, bases_from);
+        self.observer.observe(Event::Blossom {edge: e.clone(), base: b, bases: [bases_to.as_slice(), &bases_from].concat()});
+
+        // Going around the blossom through e reaches the mirror of every base on the way with an even path.
+        let value = self.d_plus[e.from()].clone() + self.d_plus[e.to()].clone() + Finite(e.weight());
+        if value.is_overflow() {
+            self.overflow = true;
+            return;
+        }
+        let mut even = Vec::new();
+        for (bases, closing) in [(bases_to, e.clone()), (bases_from, e.reverse())] {
+            for u in bases {
+                let m = self.mirror(u);
+                self.d_plus[m] = value.clone() - self.d_plus[u].clone();
+                self.blossom_pred[m] = Some(closing.clone());
+                self.basis.set_base(u, b);
+                self.basis.set_base(m, b);
+                self.observer.observe(DPlus {vertex: m, value: self.d_plus[m].clone()});
+                self.observer.observe(SetBase {vertex: u, base: b});
+                self.observer.observe(SetBase {vertex: m, base: b});
+                even.push(m);
+            }
+        }
 
-        for u in s1 {
+        for u in even {
             self.scan(u);
         }
-        for v in s2 {
-            self.scan(v);
-        }
     }
 
-    fn backtrack_blossom(&mut self, e: &E) -> (usize, Vec<E>, Vec<E>) {
-        let mut p1: Vec<E> = vec![e.reverse()];
-        let mut p2: Vec<E> = vec![e.clone()];
-
-        let mut u = self.basis[e.to()];
-        let mut v = self.basis[e.from()];
-
-        self.in_current_blossom[u] = true;
-        self.in_current_blossom[v] = true;
-
-        loop {
-            if u != self.s {
-                u = self.basis[self.mirror(u)];
-                self.in_current_blossom[u] = true;
-                let e = self.pred[u].clone().expect(format!("    Tried to unwrap pred[{}], but it's not defined!", u).as_str());
-                u = self.basis[e.from()];
-                p1.push(e);
-
-                if self.in_current_blossom[u] {
-                    p1.pop();
-                    self.in_current_blossom[u] = false;
-                    while let Some(e) = p2.last() {
-                        let vv = self.basis[e.from()];
-                        self.in_current_blossom[vv] = false;
-                        p2.pop();
-                        if vv == u { break; }
-                    }
-                    return (u, p1, p2);
+    // Find the base of the blossom closed by e, and the outermost bases on the way there from each end of e.
+    fn backtrack_blossom(&mut self, e: &E) -> (usize, Vec<usize>, Vec<usize>) {
+        let mut sides = [vec![self.basis[e.to()]], vec![self.basis[e.from()]]];
+        self.in_current_blossom[sides[0][0]] = true;
+        self.in_current_blossom[sides[1][0]] = true;
+
+        let b = 'search: loop {
+            for bases in &mut sides {
+                let u = *bases.last().unwrap();
+                if u == self.s {
+                    continue;
                 }
-                self.in_current_blossom[u] = true;
-            }
-            if v != self.s {
-                v = self.basis[self.mirror(v)];
-                self.in_current_blossom[v] = true;
-                let e = self.pred[v].clone().expect(format!("    Tried to unwrap pred[{}], but it's not defined!", v).as_str());
-                v = self.basis[e.from()];
-                p2.push(e);
-
+                let m = self.mirror(u);
+                let e = self.pred[m].as_ref().unwrap_or_else(|| panic!("    Tried to unwrap pred[{}], but it's not defined!", m));
+                let v = self.basis[e.from()];
                 if self.in_current_blossom[v] {
-                    p2.pop();
-                    self.in_current_blossom[v] = false;
-                    while let Some(e) = p1.last() {
-                        let uu = self.basis[e.from()];
-                        self.in_current_blossom[uu] = false;
-                        p1.pop();
-                        if uu == v { break; }
-                    }
-                    return (v, p1, p2);
+                    break 'search v;
                 }
                 self.in_current_blossom[v] = true;
+                bases.push(v);
             }
+        };
+
+        for u in sides.iter().flatten() {
+            self.in_current_blossom[*u] = false;
         }
+        for bases in &mut sides {
+            if let Some(i) = bases.iter().position(|&u| u == b) {
+                bases.truncate(i);
+            }
+        }
+        let [bases_to, bases_from] = sides;
+        (b, bases_to, bases_from)
     }
 
-    fn set_edge_bases(&mut self, b: usize, path: &Vec<E>) {
-        for e in path {
-            let u = e.from();
-            let m = self.mirror(u);
-            self.basis.set_base(u, b);
-            self.basis.set_base(m, b);
+    fn mirror(&self, u: usize) -> usize {
+        if u < self.orig_n {
+            u + self.orig_n
+        } else {
+            u - self.orig_n
         }
     }
+}
 
-    fn set_blossom_values(&mut self, path: &Vec<E>) -> Vec<usize> {
-        let mut ret = Vec::new();
-        for e in path {
-            let u = e.from();
-            let v = e.to();
-            let w = e.weight();
-            self.in_current_blossom[u] = false;
-            self.in_current_blossom[v] = false;
+#[cfg(test)]
+mod test_shortest_odd_path {
+    use std::fs::read_to_string;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize,usize)]) -> UndirectedGraph<u64, BasicEdge<u64>> {
+        let mut graph = UndirectedGraph::new(n);
+        for &(u,v) in edges {
+            graph.add_edge(BasicEdge::new(u, v, 1));
+        }
+        graph
+    }
 
-            // We can set a d_minus
-            if self.d_plus[v] + Finite(w) < self.d_minus[u] {
-                self.d_minus[u] = self.d_plus[v] + Finite(w);
-                self.pred[u] = Some(e.reverse());
-            }
+    fn read(folder: &str, name: &str) -> UndirectedGraph<u64, BasicEdge<u64>> {
+        read_to_string(["data/", folder, "/", name, "/", name, ".in"].concat()).unwrap().parse().unwrap()
+    }
 
-            let m = self.mirror(u);
-            // We can set a d_plus, and scan it
-            if self.d_minus[u] < self.d_plus[m] {
-                self.d_plus[m] = self.d_minus[u];
-                ret.push(m);
-            }
+    fn cost(result: PathResult<u64, BasicEdge<u64>>) -> Cost<u64> {
+        match result {
+            Possible {cost, ..} => Finite(cost),
+            Impossible => Infinite,
         }
+    }
 
-        ret
+    // Every sink in a bipartite graph is either reached before its mirror, or has no odd path at all.
+    #[test]
+    fn test_single_search() {
+        let path: Vec<(usize,usize)> = (0..199).map(|u| (u, u+1)).collect();
+        let tree: Vec<(usize,usize)> = (1..200).map(|u| ((u-1) / 2, u)).collect();
+        for graph in [graph(200, &path), graph(200, &tree), graph(6, &[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]), read("special_graphs", "grid5")] {
+            for s in [0, graph.n() / 2] {
+                let (dist, path_to, searches) = odd_paths_from(&graph, s);
+                assert_eq!(1, searches, "Needed more than one search from {}", s);
+                for t in graph.vertices() {
+                    let expected = cost(shortest_odd_path(&graph, s, t));
+                    assert_eq!(expected, dist[t]);
+                    assert_eq!(expected, cost(path_to(t)));
+                }
+            }
+        }
     }
 
-    fn mirror(&self, u: usize) -> usize {
-        if u < self.orig_n {
-            u + self.orig_n
-        } else {
-            u - self.orig_n
+    #[test]
+    fn test_has_odd_path_from() {
+        let mut graphs = vec![
+            graph(8, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6), (6, 6)]),
+            graph(7, &[(0, 1), (1, 2), (2, 3), (3, 0), (2, 4), (4, 5), (5, 6), (6, 4), (1, 1)]),
+        ];
+        for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+            graphs.push(read("small_graphs", name));
+        }
+        for name in ["gamma4", "petersen", "grid5", "parallel"] {
+            graphs.push(read("special_graphs", name));
+        }
+        for graph in graphs {
+            for s in graph.vertices() {
+                let possible = has_odd_path_from(&graph, s);
+                for t in graph.vertices() {
+                    assert_eq!(s != t && explain_impossible(&graph, s, t).is_none(), possible[t], "Disagreed with explain_impossible from {} to {}", s, t);
+                }
+            }
         }
     }
 }
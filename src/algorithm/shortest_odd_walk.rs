@@ -1,6 +1,6 @@
 use std::cmp::Reverse;
 use crate::structure::{
-    graph::undirected_graph::UndirectedGraph,
+    graph::Graph,
     cost::{Cost, Cost::*},
     path_result::{PathResult, PathResult::*},
 };
@@ -15,14 +15,14 @@ In: an undirected graph G, and two vertices s,t in V(G)
 Out: the shortest s-t-walk in G, that uses an odd number of edges
 */
 
-pub fn shortest_odd_walk<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize, t: usize) -> PathResult<W, E> {
+pub fn shortest_odd_walk<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> PathResult<W, E> {
     let n = graph.n();
     let mut even_dist: Vec<Cost<W>> = repeat(n, Infinite);
     let mut odd_dist = repeat(n, Infinite);
-    even_dist[s] = Finite(0.into());
-    let mut queue: BinaryHeap<(Reverse<Order<W>>, bool, usize)> = BinaryHeap::from([(Reverse(Order(0.into())), true, s)]);
-    let mut even_prev: Vec<Option<&E>> = repeat(n, None);
-    let mut odd_prev: Vec<Option<&E>> = repeat(n, None);
+    even_dist[s] = Finite(W::zero());
+    let mut queue: BinaryHeap<(Reverse<Order<W>>, bool, usize)> = BinaryHeap::from([(Reverse(Order(W::zero())), true, s)]);
+    let mut even_prev: Vec<Option<E>> = repeat(n, None);
+    let mut odd_prev: Vec<Option<E>> = repeat(n, None);
     let mut even_done: Vec<bool> = repeat(n, false);
     let mut odd_done: Vec<bool> = repeat(n, false);
 
@@ -30,38 +30,40 @@ pub fn shortest_odd_walk<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s:
         if even {
             if even_done[u] { continue }
             even_done[u] = true;
-            for e in &graph[&u] {
-                let dist_v = dist_u + e.weight();
-                if Finite(dist_v) < odd_dist[e.to()] {
-                    odd_dist[e.to()] = Finite(dist_v);
-                    queue.push((Reverse(Order(dist_v)), false, e.to()));
-                    odd_prev[e.to()] = Some(e);
+            for e in graph.neighbours(u) {
+                let dist_v = dist_u.clone() + e.weight();
+                let v = e.to();
+                if Finite(dist_v.clone()) < odd_dist[v] {
+                    odd_dist[v] = Finite(dist_v.clone());
+                    queue.push((Reverse(Order(dist_v)), false, v));
+                    odd_prev[v] = Some(e);
                 }
             }
         }
         else {
             if odd_done[u] { continue }
             odd_done[u] = true;
-            for e in &graph[&u] {
-                let dist_v = dist_u + e.weight();
-                if Finite(dist_v) < even_dist[e.to()] {
-                    even_dist[e.to()] = Finite(dist_v);
-                    queue.push((Reverse(Order(dist_v)), true, e.to()));
-                    even_prev[e.to()] = Some(e);
+            for e in graph.neighbours(u) {
+                let dist_v = dist_u.clone() + e.weight();
+                let v = e.to();
+                if Finite(dist_v.clone()) < even_dist[v] {
+                    even_dist[v] = Finite(dist_v.clone());
+                    queue.push((Reverse(Order(dist_v)), true, v));
+                    even_prev[v] = Some(e);
                 }
             }
         }
         if odd_dist[t].is_finite() { break; }
     }
 
-    match odd_dist[t] {
+    match odd_dist[t].clone() {
         Infinite => Impossible,
         Finite(cost) => {
-            let mut path: Vec<E> = vec![odd_prev[t].unwrap().clone()];
+            let mut path: Vec<E> = vec![odd_prev[t].clone().unwrap()];
             let mut v = path[0].from();
             while v != s {
-                let e = even_prev[v].unwrap().clone();
-                let o = odd_prev[e.from()].unwrap().clone();
+                let e = even_prev[v].clone().unwrap();
+                let o = odd_prev[e.from()].clone().unwrap();
                 v = o.from();
                 path.push(e);
                 path.push(o);
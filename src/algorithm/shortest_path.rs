@@ -3,7 +3,7 @@ use std::collections::BinaryHeap;
 use queues::{IsQueue, Queue};
 use crate::structure::cost::{Cost, Cost::*};
 use crate::structure::graph::edge::Edge;
-use crate::structure::graph::undirected_graph::UndirectedGraph;
+use crate::structure::graph::Graph;
 use crate::structure::path_result::PathResult;
 use crate::structure::path_result::PathResult::{Impossible, Possible};
 use crate::structure::weight::{Order, Weight};
@@ -14,12 +14,12 @@ Problem: Shortest Path
 In: an undirected graph G, two vertices s and t
 Out: an s-t-path in G of minimum cost, if one exists
 */
-pub fn shortest_path<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize, t: usize) -> PathResult<W,E> {
+pub fn shortest_path<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize, t: usize) -> PathResult<W,E> {
     let mut dist = repeat(graph.n(), Infinite);
     let mut done = repeat(graph.n(), false);
     let mut prev: Vec<Option<E>> = repeat(graph.n(), None);
-    dist[s] = Finite(0.into());
-    let mut pqv: BinaryHeap<(Reverse<Order<W>>, usize)> = BinaryHeap::from([(Reverse(Order(0.into())), s)]);
+    dist[s] = Finite(W::zero());
+    let mut pqv: BinaryHeap<(Reverse<Order<W>>, usize)> = BinaryHeap::from([(Reverse(Order(W::zero())), s)]);
     while let Some((Reverse(Order(d)), u)) = pqv.pop() {
         if ! done[u] {
             if u == t {
@@ -34,11 +34,11 @@ pub fn shortest_path<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usi
                 };
             }
             done[u] = true;
-            for e in &graph[&u] {
+            for e in graph.neighbours(u) {
                 let v = e.to();
-                let dv = d + e.weight();
-                if Finite(dv) < dist[v] {
-                    dist[v] = Finite(dv);
+                let dv = d.clone() + e.weight();
+                if Finite(dv.clone()) < dist[v] {
+                    dist[v] = Finite(dv.clone());
                     prev[v] = Some(e.clone());
                     pqv.push((Reverse(Order(dv)), v));
                 }
@@ -49,19 +49,19 @@ pub fn shortest_path<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usi
     return Impossible;
 }
 
-pub fn all_shortest_paths<W: Weight, E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize) -> Vec<Cost<W>> {
+pub fn all_shortest_paths<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> Vec<Cost<W>> {
     let mut dist = repeat(graph.n(), Infinite);
     let mut done = repeat(graph.n(), false);
-    dist[s] = Finite(0.into());
-    let mut pqv: BinaryHeap<(Reverse<Order<W>>, usize)> = BinaryHeap::from([(Reverse(Order(0.into())), s)]);
+    dist[s] = Finite(W::zero());
+    let mut pqv: BinaryHeap<(Reverse<Order<W>>, usize)> = BinaryHeap::from([(Reverse(Order(W::zero())), s)]);
     while let Some((Reverse(Order(d)), u)) = pqv.pop() {
         if ! done[u] {
             done[u] = true;
-            for e in &graph[&u] {
+            for e in graph.neighbours(u) {
                 let v = e.to();
-                let dv = d + e.weight();
-                if Finite(dv) < dist[v] {
-                    dist[v] = Finite(dv);
+                let dv = d.clone() + e.weight();
+                if Finite(dv.clone()) < dist[v] {
+                    dist[v] = Finite(dv.clone());
                     pqv.push((Reverse(Order(dv)), v));
                 }
             }
@@ -78,24 +78,57 @@ Out: the minimum number of edges necessary to reach each vertex
 
 Alternatively, the cost of the shortest path if we ignore the weights.
 */
-pub fn bfs<W,E>(graph: &UndirectedGraph<W,E>, s: usize) -> Vec<Cost<u64>>
+pub fn bfs<W,E,G>(graph: &G, s: usize) -> Vec<Cost<u64>>
     where W: Weight,
           E: Edge<W>,
+          G: Graph<W,E>,
 {
     let mut dist = repeat(graph.n(), Infinite);
+    let mut pred = repeat(graph.n(), None);
+    search_breadth_first(graph, s, &mut dist, &mut pred);
+    dist
+}
+
+/**
+Problem: Breadth First Forest
+In: an undirected graph G
+Out: for every vertex, the minimum number of edges necessary to reach it from the root of its component, and the last edge on such a path
+
+The root of each component is its smallest vertex.
+*/
+pub fn bfs_forest<W,E,G>(graph: &G) -> (Vec<Cost<u64>>, Vec<Option<E>>)
+    where W: Weight,
+          E: Edge<W>,
+          G: Graph<W,E>,
+{
+    let mut dist = repeat(graph.n(), Infinite);
+    let mut pred = repeat(graph.n(), None);
+    for root in graph.vertices() {
+        if dist[root].is_infinite() {
+            search_breadth_first(graph, root, &mut dist, &mut pred);
+        }
+    }
+    (dist, pred)
+}
+
+// Reach everything in the component of s that has not been reached already.
+fn search_breadth_first<W,E,G>(graph: &G, s: usize, dist: &mut [Cost<u64>], pred: &mut [Option<E>])
+    where W: Weight,
+          E: Edge<W>,
+          G: Graph<W,E>,
+{
     let mut q: Queue<(usize, u64)> = Queue::new();
     q.add((s, 0)).unwrap();
     dist[s] = Finite(0);
 
     while let Ok((u,d)) = q.remove() {
-        for e in &graph[&u] {
+        for e in graph.neighbours(u) {
             let v = e.to();
             if dist[v].is_infinite() {
                 dist[v] = Finite(d + 1);
+                pred[v] = Some(e);
                 q.add((v, d+1)).unwrap();
             }
         }
     }
-
-    dist
 }
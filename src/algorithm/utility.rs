@@ -1,11 +1,15 @@
 use std::collections::BTreeSet;
+use crate::structure::graph::csr_graph::CsrGraph;
 use crate::structure::graph::edge::{Edge};
+use crate::structure::graph::Graph;
 use crate::structure::graph::undirected_graph::UndirectedGraph;
 use crate::structure::weight::{Weight};
+use crate::utility::misc::repeat;
 
-pub fn split_edges<W, E>(g: &UndirectedGraph<W,E>, f: Vec<E>) -> (UndirectedGraph<W,E>, impl Fn(&E) -> Option<E>)
+pub fn split_edges<W, E, G>(g: &G, f: Vec<E>) -> (UndirectedGraph<W,E>, impl Fn(&E) -> Option<E>)
     where W: Weight,
           E: Edge<W>,
+          G: Graph<W,E>,
 {
     // Make sure that all the banned edges are ordered, so we can check other edges quicker
     // let bans: BTreeSet<(usize,usize)> = f.into_iter().map(|(u,v)| if v < u {(v,u)} else {(u,v)} ).collect();
@@ -15,18 +19,18 @@ pub fn split_edges<W, E>(g: &UndirectedGraph<W,E>, f: Vec<E>) -> (UndirectedGrap
     let new_n = g.n() + extra;
     let mut m = g.n();
     let mut map = Vec::new();
-    let mut split = UndirectedGraph::new(new_n);
+    let mut split = UndirectedGraph::new(new_n).allow_zero_weights();
 
     for u in g.vertices() {
-        for e in g[&u].iter().filter(|&e| e.from() < e.to()) {
-            if bans.contains(e) {
-                split.add_edge(e.clone());
+        for e in g.neighbours(u).filter(|e| e.from() < e.to()) {
+            if bans.contains(&e) {
+                split.add_edge(e);
             }
             else {
                 let (a, b) = e.subdivide(m);
                 split.add_edge(a);
                 split.add_edge(b);
-                map.push(e.clone());
+                map.push(e);
                 m += 1;
             }
         }
@@ -52,20 +56,90 @@ pub fn split_edges<W, E>(g: &UndirectedGraph<W,E>, f: Vec<E>) -> (UndirectedGrap
     })
 }
 
-pub fn create_mirror_graph<W: Weight,E: Edge<W>>(graph: &UndirectedGraph<W,E>, s: usize, t: usize) -> UndirectedGraph<W,E> {
+// Copy the graph, and attach a new vertex to u with a single edge of cost 0.
+// Returns None if u has no neighbours to copy the edge type from.
+pub fn attach_pendant<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, u: usize) -> Option<(CsrGraph<W,E>, usize)> {
+    let e = graph.neighbours(u).next()?.reverse();
+    let p = graph.n();
+    let (_, link) = e.subdivide(p);
+    let neighbourhoods = graph.vertices().map(|v| {
+        let mut neighbours: Vec<E> = graph.neighbours(v).collect();
+        if v == u {
+            neighbours.push(link.reverse());
+        }
+        neighbours
+    });
+    Some((CsrGraph::from_neighbourhoods(neighbourhoods.chain([vec![link.clone()]])), p))
+}
+
+// Copy the graph, followed by a mirror of it where every vertex u becomes u + n.
+// The search leaves out the mirrors of s and t itself, so the same mirror graph can be used for any pair of them.
+pub fn create_mirror_graph<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G) -> CsrGraph<W,E> {
     let orig_n = graph.n();
-    let new_n = orig_n * 2;
-    let mut mirror = UndirectedGraph::new(new_n);
-    for u in graph.vertices() {
-        mirror[&u] = graph[&u].clone();
-        if u != s && u != t {
-            mirror[&(u + orig_n)] = graph[&u].iter()
-                .filter(|e| e.to() != s && e.to() != t)
-                .map(|e| e.shift_by(orig_n as i64))
-                .collect()
+    let originals = graph.vertices().map(|u| graph.neighbours(u).collect::<Vec<E>>());
+    let mirrors = graph.vertices().map(|u| {
+        graph.neighbours(u)
+            .map(|e| e.shift_by(orig_n as i64))
+            .collect::<Vec<E>>()
+    });
+    CsrGraph::from_neighbourhoods(originals.chain(mirrors))
+}
+
+// The blocks (biconnected components) of the component of s, each as a list of vertices.
+// Loops are ignored, while parallel edges form a block of their own.
+pub fn blocks<W: Weight, E: Edge<W>, G: Graph<W,E>>(graph: &G, s: usize) -> Vec<Vec<usize>> {
+    let adj: Vec<Vec<usize>> = graph.vertices()
+        .map(|u| graph.neighbours(u).map(|e| e.to()).filter(|&v| v != u).collect())
+        .collect();
+    let mut disc = repeat(graph.n(), usize::MAX);
+    let mut low = repeat(graph.n(), 0);
+    let mut time = 1;
+    let mut edges: Vec<(usize,usize)> = Vec::new();
+    let mut blocks = Vec::new();
+
+    // Every frame is a vertex, its parent, the next neighbour to look at, and whether we have skipped the edge to the parent yet.
+    let mut frames = vec![(s, usize::MAX, 0, false)];
+    disc[s] = 0;
+    while let Some(&(u, p, i, skipped)) = frames.last() {
+        if i < adj[u].len() {
+            let top = frames.len() - 1;
+            frames[top].2 += 1;
+            let v = adj[u][i];
+            if v == p && ! skipped {
+                frames[top].3 = true;
+            }
+            else if disc[v] == usize::MAX {
+                disc[v] = time;
+                low[v] = time;
+                time += 1;
+                edges.push((u, v));
+                frames.push((v, u, 0, false));
+            }
+            else if disc[v] < disc[u] {
+                low[u] = low[u].min(disc[v]);
+                edges.push((u, v));
+            }
+        }
+        else {
+            frames.pop();
+            if p != usize::MAX {
+                low[p] = low[p].min(low[u]);
+                if low[u] >= disc[p] {
+                    let mut block = Vec::new();
+                    while let Some((a, b)) = edges.pop() {
+                        block.extend([a, b]);
+                        if (a, b) == (p, u) {
+                            break;
+                        }
+                    }
+                    block.sort();
+                    block.dedup();
+                    blocks.push(block);
+                }
+            }
         }
     }
-    mirror
+    blocks
 }
 
 #[cfg(test)]
@@ -81,7 +155,7 @@ mod test_utility {
             .unwrap();
 
         println!("{:?}", g);
-        println!("{:?}", create_mirror_graph(&g, 0, 4));
+        println!("{:?}", create_mirror_graph(&g));
     }
 
     #[test]
@@ -104,4 +178,33 @@ mod test_utility {
 
         assert_eq!(split.n(), g.n() + g.m() - b);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pendant() {
+        let g: UndirectedGraph<u64,BasicEdge<u64>> = std::fs::read_to_string("data/small_graphs/small1/small1.in")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let (pendant, p) = attach_pendant(&g, 2).unwrap();
+
+        assert_eq!(p, g.n());
+        assert_eq!(pendant.n(), g.n() + 1);
+        assert_eq!(pendant.m(), g.m() + 1);
+        assert_eq!(pendant.neighbours(p).collect::<Vec<_>>(), vec![BasicEdge::new(p, 2, 0)]);
+        assert!(pendant.is_adjacent(2, p));
+
+        assert!(attach_pendant(&UndirectedGraph::<u64,BasicEdge<u64>>::new(3), 1).is_none());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let mut g: UndirectedGraph<u64,BasicEdge<u64>> = UndirectedGraph::new(8);
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 5), (5, 5), (5, 6), (6, 5)] {
+            g.add_edge(BasicEdge::new(u, v, 1));
+        }
+        let mut found = blocks(&g, 3);
+        found.sort();
+        assert_eq!(found, vec![vec![0, 1, 2], vec![2, 3, 4], vec![4, 5], vec![5, 6]]);
+        assert_eq!(blocks(&g, 7), Vec::<Vec<usize>>::new());
+    }
+}
@@ -5,7 +5,7 @@ pub mod utility;
 pub mod algorithm;
 
 use shortest_odd_path::structure::graph::planar_graph::PlanarGraph;
-use shortest_odd_path::algorithm::network_diversion::network_diversion;
+use shortest_odd_path::algorithm::network_diversion::{network_diversion, DiversionResult::*};
 
 use std::env;
 use std::time::Instant;
@@ -15,7 +15,10 @@ fn parse_graph(filename: &str) -> PlanarGraph<f64> {
     read_to_string(filename)
         .expect("Could not find the graph")
         .parse()
-        .expect("Could not read the graph")
+        .unwrap_or_else(|err| {
+            eprintln!("Could not read the graph: {}", err);
+            std::process::exit(1);
+        })
 }
 
 fn main() {
@@ -41,10 +44,13 @@ fn main() {
     let start_time = Instant::now();
 
 
-    if let Some((W, v)) = network_diversion(&graph, s, t, (b1,b2)) {
-        println!("{:.2}", W);
-    } else {
-        println!("No found");
+    match network_diversion(&graph, s, t, (b1,b2)) {
+        Ok(Diverted {cost, diversion: _}) => println!("{:.2}", cost),
+        Ok(Undivertable {reason}) => println!("No found: {}", reason),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
     }
     let duration = start_time.elapsed();
     println!("Time taken: {} ms", duration.as_millis());
@@ -7,12 +7,13 @@ pub use observer_base::ObserverBase;
 
 use std::ops::Index;
 
-pub trait Basis: Index<usize> + Debug
-    where <Self as Index<usize>>::Output: PartialEq,
-{
+// Indexing a vertex gives its base, which is a vertex too.
+pub trait Basis: Index<usize, Output = usize> + Debug {
     fn new(n: usize) -> Self;
     fn get_base(&self, u: usize) -> &usize;
     fn set_base(&mut self, u: usize, new_base: usize);
+    // Put every vertex back in a set of its own, as if it was new.
+    fn reset(&mut self);
     fn same_base(&self, u: usize, v: usize) -> bool {
         self[u] == self[v] 
     }
@@ -24,9 +25,7 @@ mod test_base {
     use crate::structure::basis::unionfind_base::UnionFindBase;
     use super::*;
 
-    fn test_basis<B: Basis>()
-        where <B as Index<usize>>::Output: PartialEq
-    {
+    fn test_basis<B: Basis>() {
         let mut base = B::new(10);
         
         assert!( ! base.same_base(0, 1));
@@ -65,6 +64,25 @@ mod test_base {
         assert!(base.same_base(4, 11));
         assert!(base.same_base(4, 12));
         assert!(base.same_base(6, 10));
+
+        // The base of the merged set is always the new base, even when the other set is larger.
+        base = B::new(6);
+        base.set_base(0, 1);
+        base.set_base(2, 1);
+        base.set_base(1, 5);
+        assert_eq!(*base.get_base(0), 5);
+        assert_eq!(*base.get_base(2), 5);
+        assert!(base.same_base(1, 5));
+        assert!( ! base.same_base(3, 4));
+
+        base.reset();
+        for u in 0..6 {
+            assert_eq!(*base.get_base(u), u);
+        }
+        base.set_base(3, 4);
+        base.set_base(0, 3);
+        assert!(base.same_base(0, 4));
+        assert!( ! base.same_base(1, 5));
     }
 
     #[test]
@@ -18,6 +18,9 @@ impl Basis for ObserverBase {
     fn get_base(&self, u: usize) -> &usize {
         &self.basis[u]
     }
+    fn reset(&mut self) {
+        *self = ObserverBase::new(self.basis.len());
+    }
     fn set_base(&mut self, u: usize, new_base: usize) {
         if u == new_base { return; }
         if new_base != self.basis[new_base] {
@@ -1,41 +1,89 @@
+use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
 use std::ops::Index;
 use crate::structure::basis::Basis;
-use std::ptr::write;
+use crate::utility::misc::repeat;
 
+/**
+A union-find forest with path compression and union by rank, so that every operation takes O(α(n)) amortized time.
+
+The representative of a set is whichever root won the union, so the base of every set is stored separately at its representative.
+Path compression happens while looking up a base through &self, which is why the parents are kept in Cells.
+Only the roots that have been united ever get another parent, so they are remembered to reset just those afterwards.
+*/
 pub struct UnionFindBase {
-    basis: Vec<usize>,
+    parent: Vec<Cell<usize>>,
+    rank: Vec<u32>,
+    base: Vec<usize>,
+    united: Vec<usize>,
+}
+
+impl UnionFindBase {
+    fn find(&self, u: usize) -> usize {
+        let mut root = u;
+        while self.parent[root].get() != root {
+            root = self.parent[root].get();
+        }
+        // Path compression.
+        let mut v = u;
+        while v != root {
+            v = self.parent[v].replace(root);
+        }
+        root
+    }
 }
 
 impl Basis for UnionFindBase {
     fn new(n: usize) -> Self {
         UnionFindBase {
-            basis: (0..n).collect(),
+            parent: (0..n).map(Cell::new).collect(),
+            rank: repeat(n, 0),
+            base: (0..n).collect(),
+            united: Vec::new(),
         }
     }
 
-    fn get_base(&self, u: usize) -> &usize {
-        if u != self.basis[u] {
-            let base_base = self[self.basis[u]];
-            unsafe {
-                // Path compression.
-                // The vector is invisible to the outside world, therefore it should be safe to mutate it here.
-                write((self.basis.as_ptr() as *mut usize).add(u), base_base);
-            }
+    fn reset(&mut self) {
+        for u in self.united.drain(..) {
+            self.parent[u].set(u);
+            self.rank[u] = 0;
+            self.base[u] = u;
         }
-        return &self.basis[u];
     }
 
-    fn set_base(&mut self, u: usize, new_base: usize) { self.basis[u] = new_base; }
+    fn get_base(&self, u: usize) -> &usize {
+        &self.base[self.find(u)]
+    }
+
+    fn set_base(&mut self, u: usize, new_base: usize) {
+        let (a, b) = (self.find(u), self.find(new_base));
+        if a == b {
+            return;
+        }
+        let base = self.base[b];
+        self.united.extend([a, b]);
+        let root = if self.rank[a] > self.rank[b] {
+            self.parent[b].set(a);
+            a
+        }
+        else {
+            self.parent[a].set(b);
+            if self.rank[a] == self.rank[b] {
+                self.rank[b] += 1;
+            }
+            b
+        };
+        self.base[root] = base;
+    }
 }
 
 impl Index<usize> for UnionFindBase {
     type Output = usize;
-    fn index(&self, index: usize) -> &Self::Output { &self.get_base(index) }
+    fn index(&self, index: usize) -> &Self::Output { self.get_base(index) }
 }
 
 impl Debug for UnionFindBase {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", (0..self.basis.len()).map(|u| self[u]).collect::<Vec<usize>>())
+        write!(f, "{:?}", (0..self.base.len()).map(|u| self[u]).collect::<Vec<usize>>())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,8 @@
+pub enum Bipartition<E> {
+    Bipartite {
+        colouring: Vec<bool>,
+    },
+    NonBipartite {
+        odd_cycle: Vec<E>,
+    },
+}
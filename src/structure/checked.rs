@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use num::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+use crate::structure::weight::{Overflowing, Weight};
+use Checked::*;
+
+/**
+A weight that notices when its arithmetic overflows, instead of wrapping around like the primitive integers do in release builds.
+
+An overflowed value stays overflowed through any further arithmetic, and is not comparable to anything, not even itself.
+The algorithms spot it through Overflowing, and try_shortest_odd_path and network_diversion report it as an error instead of returning a wrong answer.
+*/
+#[derive(Debug, Clone, Copy)]
+pub enum Checked<T> {
+    Value(T),
+    Overflow,
+}
+
+impl <T> Checked<T> {
+    pub fn value(self) -> Option<T> {
+        match self {
+            Value(x) => Some(x),
+            Overflow => None,
+        }
+    }
+
+    pub fn is_overflow(&self) -> bool {
+        matches!(self, Overflow)
+    }
+
+    fn combine(self, other: Self, op: impl FnOnce(&T, &T) -> Option<T>) -> Self {
+        match (self, other) {
+            (Value(a), Value(b)) => op(&a, &b).map_or(Overflow, Value),
+            _ => Overflow,
+        }
+    }
+}
+
+impl <T> Overflowing for Checked<T> {
+    fn is_overflow(&self) -> bool { Checked::is_overflow(self) }
+}
+
+impl <T: CheckedAdd> Add for Checked<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { self.combine(rhs, T::checked_add) }
+}
+
+impl <T: CheckedSub> Sub for Checked<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { self.combine(rhs, T::checked_sub) }
+}
+
+impl <T: CheckedMul> Mul for Checked<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output { self.combine(rhs, T::checked_mul) }
+}
+
+impl <T: CheckedDiv> Div for Checked<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output { self.combine(rhs, T::checked_div) }
+}
+
+impl <T: Weight + CheckedAdd> Zero for Checked<T> {
+    fn zero() -> Self { Value(T::zero()) }
+    fn is_zero(&self) -> bool {
+        matches!(self, Value(x) if x.is_zero())
+    }
+}
+
+impl <T: Weight + CheckedMul> One for Checked<T> {
+    fn one() -> Self { Value(T::one()) }
+}
+
+impl <T: PartialEq> PartialEq for Checked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value(a), Value(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl <T: PartialOrd> PartialOrd for Checked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Value(a), Value(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl <T: Default> Default for Checked<T> {
+    fn default() -> Self { Value(T::default()) }
+}
+
+impl <T: FromStr> FromStr for Checked<T> {
+    type Err = T::Err;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Value)
+    }
+}
+
+impl <T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value(x) => write!(f, "{}", x),
+            Overflow => write!(f, "overflow"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_checked {
+    use crate::structure::checked::{Checked, Checked::*};
+    use crate::structure::weight::Overflowing;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::todo::Todo::{Blossom, Vertex};
+
+    #[test]
+    fn test_checked() {
+        let max: Checked<u32> = Value(u32::MAX);
+        assert_eq!(Value(3), Value(1) + Value(2));
+        assert!((max + Value(1)).is_overflow());
+        assert!((Value(0_u32) - Value(1)).is_overflow());
+        assert!((Value(1_u32) / Value(0)).is_overflow());
+        assert!((max + Value(1) - Value(1)).is_overflow());
+        assert_eq!(Some(u32::MAX), (max - Value(1) + Value(1)).value());
+        assert_eq!(Ok(Value(42)), "42".parse::<Checked<u32>>());
+        assert!("4294967296".parse::<Checked<u32>>().is_err());
+
+        assert!(Overflowing::is_overflow(&Checked::<u32>::Overflow));
+        assert!(! Overflowing::is_overflow(&max));
+        // Only Checked overflows, a float that is not a number is a different problem.
+        assert!(! Overflowing::is_overflow(&f64::NAN));
+        assert!(Checked::<u32>::Overflow != Overflow);
+        assert!(! (Checked::Overflow > Value(0_u32)));
+        assert!(! (Checked::Overflow < Value(0_u32)));
+    }
+
+    #[test]
+    fn test_checked_todo() {
+        // Doubling the vertex would overflow, but it is still ordered correctly against the blossoms.
+        let half = Value(u32::MAX / 2 + 1);
+        let vertex = Vertex(half, 0);
+        let blossom = |w| Blossom(Value(w), BasicEdge::new(0, 0, Value(0)));
+        assert!(vertex > blossom(1));
+        assert!(vertex > blossom(u32::MAX));
+        assert!(Vertex(Value(u32::MAX / 2), 0) < blossom(u32::MAX));
+        assert!(blossom(u32::MAX) > Vertex(Value(u32::MAX / 2), 0));
+        assert_eq!(None, Vertex(Checked::Overflow, 0).partial_cmp(&blossom(1)));
+    }
+}
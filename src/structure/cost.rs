@@ -4,7 +4,7 @@ use std::fmt::{Debug, Formatter};
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 pub use Cost::*;
-use crate::structure::weight::Weight;
+use crate::structure::weight::{Overflowing, Weight};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Cost<W: Weight> {
@@ -30,7 +30,7 @@ impl <W: Weight> Cost<W> {
 
     pub fn expect(&self, msg: &str) -> W {
         match self {
-            Finite(x) => *x,
+            Finite(x) => x.clone(),
             Infinite => panic!("{}", msg),
         }
     }
@@ -45,12 +45,18 @@ impl <W: Weight> Cost<W> {
     pub fn sup_index(xs: &Vec<Cost<W>>) -> Option<(W, usize)> {
         (0..xs.len())
             .filter(|i| xs[*i].is_finite())
-            .map(|i| (xs[i], i))
+            .map(|i| (&xs[i], i))
             .max()
             .map(|(c,i)| (c.unwrap(), i))
     }
 }
 
+impl <W: Weight> Overflowing for Cost<W> {
+    fn is_overflow(&self) -> bool {
+        matches!(self, Finite(x) if x.is_overflow())
+    }
+}
+
 impl <W: Weight> PartialOrd for Cost<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
@@ -100,7 +106,7 @@ impl <W: Weight> Into<Option<W>> for Cost<W> {
     }
 }
 
-impl <W: Weight> From<Option<u32>> for Cost<W> {
+impl <W: Weight + From<u32>> From<Option<u32>> for Cost<W> {
     fn from(value: Option<u32>) -> Self {
         match value {
             None => Infinite,
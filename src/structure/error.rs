@@ -0,0 +1,205 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::structure::graph::planar_graph::MAX_CROSSINGS;
+use Error::*;
+
+/**
+Everything that can go wrong while parsing a graph, or when an algorithm is handed input that does not fit the graph,
+or costs that do not fit the weight type.
+
+Line numbers count every line of the input from 1, including comments and blank lines.
+Errors from parsing a single edge always report line 1, until the graph parser moves them to the right line with `on_line`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    MissingValue {
+        line: usize,
+        expected: &'static str,
+    },
+    InvalidValue {
+        line: usize,
+        expected: &'static str,
+        found: String,
+    },
+    UnexpectedEnd {
+        expected: &'static str,
+    },
+    UnknownVertex {
+        line: usize,
+        vertex: usize,
+        n: usize,
+    },
+    UndefinedVertex {
+        vertex: usize,
+    },
+    CrossingEdges {
+        crossings: usize,
+        examples: Vec<((usize,usize), (usize,usize))>,
+    },
+    IncompleteFaces {
+        from: usize,
+        to: usize,
+    },
+    InvalidRotation {
+        vertex: usize,
+    },
+    EulerMismatch {
+        n: usize,
+        m: usize,
+        f: usize,
+    },
+    NotPlanar {
+        kuratowski: Vec<(usize,usize)>,
+    },
+    VertexOutOfRange {
+        vertex: usize,
+        n: usize,
+    },
+    MissingEdge {
+        from: usize,
+        to: usize,
+    },
+    Overflow,
+    InvalidWeight {
+        line: usize,
+        weight: String,
+        reason: &'static str,
+    },
+    UnusableWeight {
+        weight: String,
+        reason: &'static str,
+    },
+}
+
+impl Error {
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            MissingValue {expected, ..} => MissingValue {line, expected},
+            InvalidValue {expected, found, ..} => InvalidValue {line, expected, found},
+            UnknownVertex {vertex, n, ..} => UnknownVertex {line, vertex, n},
+            InvalidWeight {weight, reason, ..} => InvalidWeight {line, weight, reason},
+            other => other,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissingValue {line, expected} => write!(f, "line {}: expected {} here, but found nothing", line, expected),
+            InvalidValue {line, expected, found} => write!(f, "line {}: could not parse '{}' as {}", line, found, expected),
+            UnexpectedEnd {expected} => write!(f, "expected {}, but the input ended", expected),
+            UnknownVertex {line, vertex, n} => write!(f, "line {}: vertex {} does not exist, the graph only has {} vertices", line, vertex, n),
+            UndefinedVertex {vertex} => write!(f, "vertex {} was never given any coordinates", vertex),
+            CrossingEdges {crossings, examples} => {
+                let at_least = if *crossings >= MAX_CROSSINGS { "at least " } else { "" };
+                write!(f, "this is not a straight-line embedding, found {}{} pairs of crossing edges", at_least, crossings)?;
+                for ((a,b), (c,d)) in examples {
+                    write!(f, "\n    ({}, {})  x  ({}, {})", a, b, c, d)?;
+                }
+                Ok(())
+            }
+            IncompleteFaces {from, to} => write!(f, "the edge ({}, {}) did not find both a left and right region", from, to),
+            InvalidRotation {vertex} => write!(f, "the rotation of vertex {} does not list each of its neighbours exactly once", vertex),
+            EulerMismatch {n, m, f: faces} => write!(f, "found {} regions, but Euler's formula says a planar graph with n = {} and m = {} should have {}", faces, n, m, (*m + 2).saturating_sub(*n)),
+            NotPlanar {kuratowski} => {
+                write!(f, "the graph is not planar, it contains a subdivision of K5 or K3,3 with {} edges:", kuratowski.len())?;
+                for (u, v) in kuratowski {
+                    write!(f, "\n    ({}, {})", u, v)?;
+                }
+                Ok(())
+            }
+            VertexOutOfRange {vertex, n} => write!(f, "vertex {} does not exist, the graph only has {} vertices", vertex, n),
+            MissingEdge {from, to} => write!(f, "the edge ({}, {}) does not exist", from, to),
+            Overflow => write!(f, "the cost of a path grew too large for the weight type"),
+            InvalidWeight {line, weight, reason} => write!(f, "line {}: the weight {} can not be used, it is {}", line, weight, reason),
+            UnusableWeight {weight, reason} => write!(f, "the weight {} can not be used, it is {}", weight, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Parse the next word of a line, or explain what was expected there.
+pub fn parse_next<'a, T: FromStr>(words: &mut impl Iterator<Item = &'a str>, line: usize, expected: &'static str) -> Result<T, Error> {
+    let word = words.next().ok_or(MissingValue {line, expected})?;
+    word.parse().or(Err(InvalidValue {line, expected, found: word.to_string()}))
+}
+
+#[cfg(test)]
+mod test_error {
+    use crate::structure::error::Error::*;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::planar_graph::PlanarGraph;
+    use crate::structure::graph::simple_graph_strategy::SumWeights;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+
+    fn parse(input: &str) -> Result<UndirectedGraph<u64, BasicEdge<u64>>, super::Error> {
+        input.parse()
+    }
+
+    #[test]
+    fn test_undirected_errors() {
+        assert_eq!(parse(""), Err(UnexpectedEnd {expected: "the number of vertices"}));
+        assert_eq!(parse("x"), Err(InvalidValue {line: 1, expected: "the number of vertices", found: "x".to_string()}));
+        assert_eq!(parse("3\n% comment\n0 1\n1"), Err(MissingValue {line: 4, expected: "the tip of the edge"}));
+        assert_eq!(parse("3\n0 1 heavy"), Err(InvalidValue {line: 2, expected: "the weight of the edge", found: "heavy".to_string()}));
+        assert_eq!(parse("3\n\n0 3"), Err(UnknownVertex {line: 3, vertex: 3, n: 3}));
+        assert_eq!(parse("3\n0 1 5 2"), Err(InvalidValue {line: 2, expected: "the label of the edge (0 or 1)", found: "2".to_string()}));
+        assert!(parse("3\n0 1\n1 2 5").is_ok());
+        assert!(parse("3\n0 1\n1 2 5 1").is_ok());
+    }
+
+    #[test]
+    fn test_weight_errors() {
+        let invalid = |line: usize, weight: &str, reason: &'static str| InvalidWeight {line, weight: weight.to_string(), reason};
+        let parse_f64 = |input: &str| input.parse::<UndirectedGraph<f64, BasicEdge<f64>>>();
+        assert_eq!(parse_f64("2\n0 1 NaN"), Err(invalid(2, "NaN", "not a number")));
+        assert_eq!(parse_f64("2\n0 1 inf"), Err(invalid(2, "inf", "infinite")));
+        assert_eq!(parse_f64("2\n0 1 1.5\n0 1 -0.5"), Err(invalid(3, "-0.5", "negative")));
+        assert_eq!(parse("2\n\n0 1 0"), Err(invalid(3, "0", "zero, which is only allowed if zero weights are enabled")));
+        assert!(UndirectedGraph::<u64, BasicEdge<u64>>::parse_with_zero_weights("2\n0 1 0").is_ok());
+        assert!(UndirectedGraph::<f64, BasicEdge<f64>>::parse_with_zero_weights("2\n0 1 -0.5").is_err());
+
+        let planar = |input: &str| PlanarGraph::<f64>::parse::<SumWeights>(input, true).err();
+        let zero_planar = |input: &str| PlanarGraph::<f64>::parse_with_zero_weights::<SumWeights>(input, true).err();
+        assert_eq!(zero_planar("2 1\n0 0 0\n1 1 0\n0 1 -1"), Some(invalid(4, "-1", "negative")));
+        assert_eq!(planar("2 1\n0 0 0\n1 1 0\n0 1 0"), Some(invalid(4, "0", "zero, which is only allowed if zero weights are enabled")));
+        assert_eq!(zero_planar("2 1\n0 0 0\n1 1 0\n0 1 0"), None);
+    }
+
+    #[test]
+    fn test_try_add_edge() {
+        let mut graph = UndirectedGraph::new(2);
+        assert_eq!(graph.try_add_edge(BasicEdge::new(0, 1, f64::NAN)), Err(UnusableWeight {weight: "NaN".to_string(), reason: "not a number"}));
+        assert_eq!(graph.try_add_edge(BasicEdge::new(0, 1, 0.0)), Err(UnusableWeight {weight: "0".to_string(), reason: "zero, which is only allowed if zero weights are enabled"}));
+        assert_eq!(graph.try_add_edge(BasicEdge::new(0, 2, 1.0)), Err(VertexOutOfRange {vertex: 2, n: 2}));
+        assert_eq!(graph.m(), 0);
+        assert_eq!(graph.try_add_edge(BasicEdge::new(0, 1, 1.0)), Ok(()));
+        assert_eq!(graph.m(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not add the edge (0, 1): the weight NaN can not be used, it is not a number")]
+    fn test_add_edge_nan() {
+        UndirectedGraph::new(2).add_edge(BasicEdge::new(0, 1, f64::NAN));
+    }
+
+    #[test]
+    fn test_weight_display() {
+        assert_eq!(InvalidWeight {line: 3, weight: "-1".to_string(), reason: "negative"}.to_string(), "line 3: the weight -1 can not be used, it is negative");
+        assert_eq!(UnusableWeight {weight: "-1".to_string(), reason: "negative"}.to_string(), "the weight -1 can not be used, it is negative");
+    }
+
+    #[test]
+    fn test_planar_errors() {
+        let parse = |input: &str| PlanarGraph::<f64>::parse::<SumWeights>(input, true).err();
+        assert_eq!(parse("3 1\n0 0 0\n1 1 0"), Some(UnexpectedEnd {expected: "another vertex"}));
+        assert_eq!(parse("2 1\n0 0 0\n2 1 0\n0 1"), Some(UnknownVertex {line: 3, vertex: 2, n: 2}));
+        assert_eq!(parse("2 1\n0 0 0\n0 1 0\n0 1"), Some(UndefinedVertex {vertex: 1}));
+        assert_eq!(
+            parse("4 2\n0 0 0\n1 1 1\n2 0 1\n3 1 0\n0 1\n2 3"),
+            Some(CrossingEdges {crossings: 1, examples: vec![((0, 1), (2, 3))]})
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use crate::structure::cost::Cost;
+use crate::structure::graph::edge::Edge;
+use crate::structure::weight::Weight;
+
+/**
+Everything that happens during a search for a shortest odd path, in the order it happens.
+
+The search runs on the mirror graph, where every vertex u < n has a mirror u + n.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<W: Weight, E: Edge<W>> {
+    // The search starts, looking for an odd s-t-path in a graph with n vertices.
+    Start {
+        n: usize,
+        s: usize,
+        t: usize,
+    },
+    // The vertex is scanned, and tries to improve d_minus for each of its neighbours.
+    Scan {
+        vertex: usize,
+    },
+    DPlus {
+        vertex: usize,
+        value: Cost<W>,
+    },
+    DMinus {
+        vertex: usize,
+        value: Cost<W>,
+        pred: E,
+    },
+    // The edge closes a blossom with the given base, going through all the outer bases on the way.
+    Blossom {
+        edge: E,
+        base: usize,
+        bases: Vec<usize>,
+    },
+    SetBase {
+        vertex: usize,
+        base: usize,
+    },
+    // The search is over, and found an odd path of this cost, if any.
+    Done {
+        cost: Cost<W>,
+    },
+}
+
+pub trait Observer<W: Weight, E: Edge<W>> {
+    fn observe(&mut self, event: Event<W,E>);
+}
+
+// Nobody is watching, so this costs nothing.
+impl <W: Weight, E: Edge<W>> Observer<W,E> for () {
+    fn observe(&mut self, _: Event<W,E>) {}
+}
+
+impl <W: Weight, E: Edge<W>, O: Observer<W,E> + ?Sized> Observer<W,E> for &mut O {
+    fn observe(&mut self, event: Event<W,E>) {
+        (**self).observe(event);
+    }
+}
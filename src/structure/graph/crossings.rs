@@ -0,0 +1,300 @@
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+use std::collections::BTreeSet;
+use std::ops::Bound::{Excluded, Unbounded};
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::planar_edge::{intersect, orientation, Orientation::*};
+use crate::structure::graph::point::Point;
+use crate::structure::weight::Weight;
+
+/**
+Check whether any two of the segments cross, with the Shamos-Hoey sweep in O(m log m) time.
+Returns the indices of one crossing pair if there is one.
+
+Just like `intersect`, segments that only meet in a shared endpoint do not count as crossing.
+Every pair that becomes neighbours in the sweep is checked with `intersect` itself, so a reported crossing is always a real one.
+*/
+pub fn find_crossing<W: Weight, E: Edge<W>>(points: &Vec<Point>, segments: &[E]) -> Option<(usize,usize)> {
+    let sweep = Sweep::new(points, segments);
+    let mut events = Vec::with_capacity(2 * sweep.segments.len());
+    for (i, &(a, b)) in sweep.segments.iter().enumerate() {
+        events.push((a, Insert, i));
+        if a != b {
+            events.push((b, Remove, i));
+        }
+    }
+    // Sweep from left to right, and from bottom to top along vertical lines.
+    // Everything ending in a point leaves the sweep line before anything starting there enters it.
+    events.sort_by(|(p, kind, _), (q, other, _)| lexicographic(p, q).then(kind.cmp(other)));
+
+    let mut status: BTreeSet<Active<W,E>> = BTreeSet::new();
+    for (_, kind, i) in events {
+        let segment = Active { id: i, sweep: &sweep };
+        let below = status.range(..&segment).next_back().map(|s| s.id);
+        let above = status.range((Excluded(&segment), Unbounded)).next().map(|s| s.id);
+        match kind {
+            Insert => {
+                for other in below.into_iter().chain(above) {
+                    if sweep.crossing(i, other) {
+                        return Some((i.min(other), i.max(other)));
+                    }
+                }
+                // A segment between two vertices at the same coordinates can only touch what is already on the sweep line.
+                if sweep.segments[i].0 != sweep.segments[i].1 {
+                    status.insert(segment);
+                }
+            }
+            Remove => {
+                // Nothing on the sweep line has crossed yet, so it is still in the order the segments were inserted in.
+                let removed = status.remove(&segment);
+                debug_assert!(removed, "Segment {} was not on the sweep line", i);
+                if let (Some(b), Some(a)) = (below, above) {
+                    if sweep.crossing(a, b) {
+                        return Some((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/**
+Find up to `limit` pairs of crossing segments, ordered by their indices.
+Whenever the sweep finds a crossing, every other crossing of one of the two segments is collected as well, and that segment is left out of the next sweep.
+That takes O(limit * m log m) time, and finds every crossing if the limit is high enough.
+*/
+pub fn find_crossings<W: Weight, E: Edge<W>>(points: &Vec<Point>, segments: &[E], limit: usize) -> Vec<(usize,usize)> {
+    let mut remaining: Vec<usize> = (0..segments.len()).collect();
+    let mut crossings = Vec::new();
+    while crossings.len() < limit {
+        let rest: Vec<E> = remaining.iter().map(|&i| segments[i].clone()).collect();
+        let Some((i, _)) = find_crossing(points, &rest) else { break };
+        let first = remaining.remove(i);
+        crossings.extend(remaining.iter()
+            .filter(|&&j| intersect(points, &segments[first], &segments[j]))
+            .map(|&j| (first.min(j), first.max(j)))
+            .take(limit - crossings.len()));
+    }
+    crossings.sort();
+    crossings
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    Remove,
+    Insert,
+}
+use Event::*;
+
+struct Sweep<'a, W: Weight, E: Edge<W>> {
+    points: &'a Vec<Point>,
+    edges: &'a [E],
+    // The endpoints of every segment, from left to right.
+    segments: Vec<(Point, Point)>,
+    _marker: std::marker::PhantomData<W>,
+}
+
+impl <'a, W: Weight, E: Edge<W>> Sweep<'a, W, E> {
+    fn new(points: &'a Vec<Point>, edges: &'a [E]) -> Self {
+        let segments = edges.iter()
+            .map(|e| {
+                let (a, b) = (points[e.from()], points[e.to()]);
+                if lexicographic(&a, &b) == Greater { (b, a) } else { (a, b) }
+            })
+            .collect();
+        Sweep {
+            points,
+            edges,
+            segments,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn crossing(&self, i: usize, j: usize) -> bool {
+        intersect(self.points, &self.edges[i], &self.edges[j])
+    }
+
+    /*
+    Two segments on the sweep line are ordered by which side of the first one the other one starts on,
+    or the side it goes to if it starts on the line through the first one. This does not depend on where the sweep line is,
+    so the order stays the same for as long as two segments do not cross, without computing where they meet the sweep line.
+    */
+    fn compare(&self, i: usize, j: usize) -> Ordering {
+        let ((a, b), (c, d)) = (self.segments[i], self.segments[j]);
+        if lexicographic(&c, &a) == Less {
+            return self.compare(j, i).reverse();
+        }
+        let side = match orientation(&a, &b, &c) {
+            Colinear => orientation(&a, &b, &d),
+            side => side,
+        };
+        match side {
+            Counterclockwise => Less,
+            Clockwise => Greater,
+            // Only segments that lie on top of each other, which cross anyway.
+            Colinear => i.cmp(&j),
+        }
+    }
+}
+
+fn lexicographic(p: &Point, q: &Point) -> Ordering {
+    p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y))
+}
+
+// A segment on the sweep line, ordered from bottom to top where it meets the line.
+struct Active<'s, 'a, W: Weight, E: Edge<W>> {
+    id: usize,
+    sweep: &'s Sweep<'a, W, E>,
+}
+
+impl <W: Weight, E: Edge<W>> PartialEq for Active<'_, '_, W, E> {
+    fn eq(&self, other: &Self) -> bool { self.id == other.id }
+}
+impl <W: Weight, E: Edge<W>> Eq for Active<'_, '_, W, E> {}
+impl <W: Weight, E: Edge<W>> PartialOrd for Active<'_, '_, W, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl <W: Weight, E: Edge<W>> Ord for Active<'_, '_, W, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.id == other.id { Equal } else { self.sweep.compare(self.id, other.id) }
+    }
+}
+
+#[cfg(test)]
+mod test_crossings {
+    use std::fs::read_to_string;
+    use crate::structure::graph::edge::BasicEdge;
+    use super::*;
+
+    fn brute_force(points: &Vec<Point>, segments: &[BasicEdge<u64>]) -> Vec<(usize,usize)> {
+        let mut crossings = Vec::new();
+        for i in 0..segments.len() {
+            for j in i+1..segments.len() {
+                if intersect(points, &segments[i], &segments[j]) {
+                    crossings.push((i, j));
+                }
+            }
+        }
+        crossings
+    }
+
+    fn segments(pairs: &[(usize,usize)]) -> Vec<BasicEdge<u64>> {
+        pairs.iter().map(|&(u,v)| BasicEdge::new(u, v, 1)).collect()
+    }
+
+    fn assert_same(points: &Vec<Point>, segments: &[BasicEdge<u64>]) {
+        let expected = brute_force(points, segments);
+        assert_eq!(find_crossings(points, segments, usize::MAX), expected);
+        let some = find_crossings(points, segments, 3);
+        assert!(some.len() == expected.len().min(3) && some.iter().all(|pair| expected.contains(pair)), "{:?} are not crossings", some);
+        match find_crossing(points, segments) {
+            Some(pair) => assert!(expected.contains(&pair), "{:?} is not a crossing", pair),
+            None => assert!(expected.is_empty(), "Missed the crossings {:?}", expected),
+        }
+    }
+
+    #[test]
+    fn test_degenerate_segments() {
+        // A square with both diagonals, a vertical edge through the middle, and a vertex touching an edge.
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 3.0),
+            Point::new(3.0, 1.0),
+            Point::new(2.0, 1.0),
+        ];
+        assert_same(&points, &segments(&[(0, 1), (1, 2), (2, 3), (3, 0)]));
+        assert_same(&points, &segments(&[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]));
+        assert_same(&points, &segments(&[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 3)]));
+        assert_same(&points, &segments(&[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]));
+        assert_same(&points, &segments(&[(0, 4), (4, 1), (4, 5), (6, 7)]));
+        assert_same(&points, &segments(&[(1, 2), (6, 7)]));
+        assert_same(&points, &segments(&[(1, 6), (7, 6), (0, 6)]));
+        assert_eq!(find_crossing(&points, &segments(&[(0, 3), (1, 2), (4, 5)])), None);
+
+        // Edges from the same vertex that lie on top of each other.
+        assert_same(&points, &segments(&[(0, 4), (0, 1)]));
+        assert_eq!(find_crossing(&points, &segments(&[(0, 4), (0, 1)])), Some((0, 1)));
+        assert_eq!(find_crossing(&points, &segments(&[(0, 4), (4, 1)])), None);
+    }
+
+    #[test]
+    fn test_random_segments() {
+        // A small linear congruential generator, so the test does not need any extra dependencies.
+        let mut seed: u64 = 12345;
+        let mut next = |k: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % k
+        };
+        for _ in 0..2000 {
+            let n = 3 + next(8) as usize;
+            let points: Vec<Point> = (0..n).map(|_| Point::new(next(5) as f64, next(5) as f64)).collect();
+            let pairs: Vec<(usize,usize)> = (0..1 + next(10))
+                .map(|_| (next(n as u64) as usize, next(n as u64) as usize))
+                .filter(|(u,v)| u != v)
+                .collect();
+            assert_same(&points, &segments(&pairs));
+        }
+    }
+
+    #[test]
+    fn test_fractional_segments() {
+        // Coordinates like 0.1 are rounded, so points that should be on a line are often just off it.
+        let mut seed: u64 = 54321;
+        let mut next = |k: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % k
+        };
+        for _ in 0..200 {
+            let n = 3 + next(10) as usize;
+            let points: Vec<Point> = (0..n).map(|_| Point::new(next(6) as f64 * 0.1, next(6) as f64 * 0.3)).collect();
+            let pairs: Vec<(usize,usize)> = (0..1 + next(15))
+                .map(|_| (next(n as u64) as usize, next(n as u64) as usize))
+                .filter(|(u,v)| u != v)
+                .collect();
+            assert_same(&points, &segments(&pairs));
+        }
+    }
+
+    #[test]
+    fn test_limit() {
+        // A grid of 5 horizontal and 5 vertical segments, which cross in 25 places.
+        let points: Vec<Point> = (0..5)
+            .flat_map(|i| [
+                Point::new(0.0, 1.0 + i as f64),
+                Point::new(6.0, 1.0 + i as f64),
+                Point::new(1.0 + i as f64, 0.0),
+                Point::new(1.0 + i as f64, 6.0),
+            ])
+            .collect();
+        let grid = segments(&(0..10).map(|i| (2 * i, 2 * i + 1)).collect::<Vec<_>>());
+        let crossings = find_crossings(&points, &grid, 10);
+        assert_eq!(10, crossings.len());
+        assert!(crossings.iter().all(|&(i, j)| (i + j) % 2 == 1), "{:?} are not all crossings", crossings);
+        assert_eq!(25, find_crossings(&points, &grid, usize::MAX).len());
+    }
+
+    #[test]
+    fn test_delaunay_has_no_crossings() {
+        let input = read_to_string("data/delaunay_graphs/planar_delaunay_graphs/delaunay100/delaunay100.in").unwrap();
+        let mut lines = input.lines();
+        let mut header = lines.next().unwrap().split_whitespace().map(|w| w.parse::<usize>().unwrap());
+        let (n, m) = (header.next().unwrap(), header.next().unwrap());
+        let points: Vec<Point> = lines.by_ref().take(n)
+            .map(|line| {
+                let words: Vec<f64> = line.split_whitespace().skip(1).map(|w| w.parse().unwrap()).collect();
+                Point::new(words[0], words[1])
+            })
+            .collect();
+        let pairs: Vec<(usize,usize)> = lines.take(m)
+            .map(|line| {
+                let words: Vec<usize> = line.split_whitespace().take(2).map(|w| w.parse().unwrap()).collect();
+                (words[0], words[1])
+            })
+            .collect();
+        assert_same(&points, &segments(&pairs));
+    }
+}
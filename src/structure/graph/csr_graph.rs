@@ -0,0 +1,196 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::Index;
+use std::str::FromStr;
+use crate::structure::error::Error;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::graph::undirected_graph::{parse_edges, UndirectedGraph};
+use crate::structure::weight::Weight;
+
+/**
+An undirected graph in compressed sparse row form.
+All the neighbourhoods are stored back to back in a single vector, and N(u) is the slice between offsets[u] and offsets[u+1].
+
+This holds the same edges as an UndirectedGraph, but without a separate allocation for every vertex, so it is better suited for very large graphs.
+It cannot be changed after it is built.
+*/
+#[derive(PartialEq, Clone)]
+pub struct CsrGraph<W, E>
+    where W: Weight,
+          E: Edge<W>,
+{
+    offsets: Vec<usize>,
+    edges: Vec<E>,
+    _marker: PhantomData<W>,
+}
+
+impl <W: Weight, E: Edge<W>> CsrGraph<W,E> {
+    // Build the graph from a list where every undirected edge appears exactly once.
+    // The neighbourhoods keep the order of the list, just like adding the edges one by one to an UndirectedGraph.
+    pub fn from_edges(n: usize, edges: Vec<E>) -> Self {
+        let mut directed = Vec::with_capacity(2 * edges.len());
+        for e in edges {
+            let b = e.reverse();
+            directed.push(e);
+            directed.push(b);
+        }
+        directed.sort_by_key(Edge::from);
+
+        let mut offsets = vec![0; n + 1];
+        for e in &directed {
+            offsets[e.from() + 1] += 1;
+        }
+        for u in 0..n {
+            offsets[u + 1] += offsets[u];
+        }
+        CsrGraph {
+            offsets,
+            edges: directed,
+            _marker: PhantomData,
+        }
+    }
+
+    // Build the graph from the neighbourhood of each vertex, in order.
+    pub fn from_neighbourhoods<I: IntoIterator<Item = E>>(neighbourhoods: impl IntoIterator<Item = I>) -> Self {
+        let mut offsets = vec![0];
+        let mut edges = Vec::new();
+        for neighbourhood in neighbourhoods {
+            edges.extend(neighbourhood);
+            offsets.push(edges.len());
+        }
+        edges.shrink_to_fit();
+        CsrGraph {
+            offsets,
+            edges,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn n(&self) -> usize { self.offsets.len() - 1 }
+    pub fn m(&self) -> usize { self.edges.len() / 2 }
+    #[allow(non_snake_case)]
+    pub fn N(&self, u: usize) -> &[E] { &self[u] }
+    pub fn vertices(&self) -> impl Iterator<Item = usize> { 0..self.n() }
+    pub fn edges(&self) -> &[E] { &self.edges }
+}
+
+impl <W: Weight, E: Edge<W>> Graph<W,E> for CsrGraph<W,E> {
+    fn n(&self) -> usize { self.n() }
+    fn m(&self) -> usize { self.m() }
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = E> + '_ { self[u].iter().cloned() }
+}
+
+impl <W: Weight, E: Edge<W>> From<&UndirectedGraph<W,E>> for CsrGraph<W,E> {
+    fn from(graph: &UndirectedGraph<W,E>) -> Self {
+        Self::from_neighbourhoods(graph.vertices().map(|u| graph[u].iter().cloned()))
+    }
+}
+
+impl <W: Weight, E: Edge<W>> FromStr for CsrGraph<W,E> {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (n, edges) = parse_edges(str, false)?;
+        Ok(Self::from_edges(n, edges))
+    }
+}
+
+impl <W: Weight, E: Edge<W>> Index<&usize> for CsrGraph<W,E> {
+    type Output = [E];
+    fn index(&self, u: &usize) -> &Self::Output { &self.edges[self.offsets[*u]..self.offsets[*u + 1]] }
+}
+impl <W: Weight, E: Edge<W>> Index<usize> for CsrGraph<W,E> {
+    type Output = [E];
+    fn index(&self, u: usize) -> &Self::Output { &self[&u] }
+}
+
+impl <W: Weight, E: Edge<W>> Debug for CsrGraph<W,E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "CsrGraph(n = {}, m = {}):", self.n(), self.m())?;
+        for u in self.vertices() {
+            writeln!(f, "  N({}) = {:?}", u, &self[u])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_csr_graph {
+    use std::fs::read_to_string;
+    use crate::algorithm::network_diversion::network_diversion;
+    use crate::algorithm::shortest_odd_path::shortest_odd_path;
+    use crate::algorithm::shortest_odd_walk::shortest_odd_walk;
+    use crate::algorithm::shortest_path::all_shortest_paths;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::planar_edge::PlanarEdge;
+    use crate::structure::graph::planar_graph::PlanarGraph;
+    use crate::structure::path_result::PathResult::{self, *};
+    use super::*;
+
+    fn cost<E: Edge<u64>>(result: PathResult<u64, E>) -> Option<u64> {
+        match result {
+            Possible {cost, path: _} => Some(cost),
+            Impossible => None,
+        }
+    }
+
+    #[test]
+    fn test_same_graph() {
+        let input = read_to_string("data/small_graphs/small1/small1.in").unwrap();
+        let graph: UndirectedGraph<u64, BasicEdge<u64>> = input.parse().unwrap();
+        let csr: CsrGraph<u64, BasicEdge<u64>> = input.parse().unwrap();
+
+        assert_eq!(csr, CsrGraph::from(&graph));
+        assert_eq!(csr.n(), graph.n());
+        assert_eq!(csr.m(), graph.m());
+        for u in graph.vertices() {
+            assert_eq!(&csr[u], graph[u].as_slice());
+        }
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let csr: CsrGraph<u64, BasicEdge<u64>> = "3\n0 1\n1 1 5\n".parse().unwrap();
+        assert_eq!(csr.m(), 2);
+        assert_eq!(csr.N(1), &[BasicEdge::new(1, 0, 1), BasicEdge::new(1, 1, 5), BasicEdge::new(1, 1, 5)]);
+        assert!(csr.N(2).is_empty());
+    }
+
+    #[test]
+    fn test_same_answers() {
+        for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+            let graph: UndirectedGraph<u64, BasicEdge<u64>> = read_to_string(["data/small_graphs/", name, "/", name, ".in"].concat())
+                .unwrap()
+                .parse()
+                .unwrap();
+            let csr = CsrGraph::from(&graph);
+            for s in graph.vertices() {
+                assert_eq!(all_shortest_paths(&graph, s), all_shortest_paths(&csr, s));
+                for t in graph.vertices() {
+                    assert_eq!(cost(shortest_odd_walk(&graph, s, t)), cost(shortest_odd_walk(&csr, s, t)));
+                    assert_eq!(cost(shortest_odd_path(&graph, s, t)), cost(shortest_odd_path(&csr, s, t)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_diversions() {
+        let planar: PlanarGraph<f64> = read_to_string("data/planar_graphs/small_planar_graphs/small_planar1/small_planar1.in")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let csr: PlanarGraph<f64, CsrGraph<f64, PlanarEdge<f64>>> = PlanarGraph::from(&planar);
+        assert_eq!(csr.f(), planar.f());
+        for s in planar.real().vertices() {
+            for t in planar.real().vertices().filter(|&t| t != s) {
+                for d in planar.real().edges() {
+                    let expected = network_diversion(&planar, s, t, (d.from(), d.to())).unwrap().cost();
+                    let actual = network_diversion(&csr, s, t, (d.from(), d.to())).unwrap().cost();
+                    assert_eq!(expected, actual);
+                }
+            }
+        }
+    }
+}
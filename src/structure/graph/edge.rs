@@ -2,14 +2,35 @@ use std::cmp::Ordering;
 use std::cmp::Ordering::Equal;
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
+use crate::structure::error::{Error, Error::InvalidValue, parse_next};
 use crate::structure::weight::{Weight, Weighted};
 
-pub trait Edge<W: Weight>: Weighted<W> + FromStr + Debug + Clone + PartialEq + Eq + PartialOrd + Ord {
+pub trait Edge<W: Weight>: Weighted<W> + FromStr<Err = Error> + Debug + Clone + PartialEq + Eq + PartialOrd + Ord {
     fn from(&self) -> usize;
     fn to(&self) -> usize;
     fn reverse(&self) -> Self;
     fn subdivide(&self, middle: usize) -> (Self, Self);
     fn shift_by(&self, offset: i64) -> Self;
+
+    // Whether the edge is marked, for problems that care about the parity of the marked edges rather than all of them.
+    fn label(&self) -> bool { false }
+}
+
+// Parse the optional weight at the end of an edge, which defaults to 1 if it is left out.
+pub fn parse_weight<'a, W: Weight>(words: &mut impl Iterator<Item = &'a str>, line: usize) -> Result<W, Error> {
+    match words.next() {
+        None => Ok(W::one()),
+        Some(w) => w.parse().or(Err(InvalidValue {line, expected: "the weight of the edge", found: w.to_string()})),
+    }
+}
+
+// Parse the optional label after the weight, which is either 0 or 1 and defaults to 0.
+pub fn parse_label<'a>(words: &mut impl Iterator<Item = &'a str>, line: usize) -> Result<bool, Error> {
+    match words.next() {
+        None | Some("0") => Ok(false),
+        Some("1") => Ok(true),
+        Some(l) => Err(InvalidValue {line, expected: "the label of the edge (0 or 1)", found: l.to_string()}),
+    }
 }
 
 pub fn map_to<W: Weight, E: Edge<W>>(edges: &Vec<E>) -> Vec<usize> {
@@ -24,14 +45,20 @@ pub struct BasicEdge<W: Weight> {
     from: usize,
     to: usize,
     weight: W,
+    label: bool,
 }
 
 impl <W: Weight> BasicEdge<W> {
     pub fn new(from: usize, to: usize, weight: W) -> Self {
+        BasicEdge::labelled(from, to, weight, false)
+    }
+
+    pub fn labelled(from: usize, to: usize, weight: W, label: bool) -> Self {
         BasicEdge {
             from,
             to,
             weight,
+            label,
         }
     }
 }
@@ -40,7 +67,7 @@ impl <W: Weight> Eq for BasicEdge<W> {}
 
 impl<W: Weight> PartialOrd for BasicEdge<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        (self.from, self.to, self.weight).partial_cmp(&(other.from, other.to, other.weight))
+        (self.from, self.to, &self.weight, self.label).partial_cmp(&(other.from, other.to, &other.weight, other.label))
     }
 }
 
@@ -57,7 +84,8 @@ impl <W: Weight> Edge<W> for BasicEdge<W> {
         BasicEdge {
             from: self.to,
             to: self.from,
-            weight: self.weight,
+            weight: self.weight.clone(),
+            label: self.label,
         }
     }
     fn subdivide(&self, middle: usize) -> (Self, Self) {
@@ -65,12 +93,14 @@ impl <W: Weight> Edge<W> for BasicEdge<W> {
             BasicEdge {
                 from: self.from,
                 to: middle,
-                weight: self.weight,
+                weight: self.weight.clone(),
+                label: self.label,
             },
             BasicEdge {
                 from: middle,
                 to: self.to,
-                weight: 0.into(),
+                weight: W::zero(),
+                label: false,
             }
         )
     }
@@ -79,44 +109,44 @@ impl <W: Weight> Edge<W> for BasicEdge<W> {
         BasicEdge {
             from: (self.from as i64 + offset) as usize,
             to: (self.to as i64 + offset) as usize,
-            weight: self.weight,
+            weight: self.weight.clone(),
+            label: self.label,
         }
     }
+
+    fn label(&self) -> bool { self.label }
 }
 
 impl <W: Weight> Debug for BasicEdge<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.weight == 1.into() {
-            write!(f, "{} --> {}", self.from, self.to)
+        let line = if self.label { "=" } else { "-" };
+        if self.weight == W::one() {
+            write!(f, "{} {}{}> {}", self.from, line, line, self.to)
         }
         else {
-            write!(f, "{} -{}-> {}", self.from, self.weight, self.to)
+            write!(f, "{} {}{}{}> {}", self.from, line, self.weight, line, self.to)
         }
     }
 }
 
 impl <W: Weight> Weighted<W> for BasicEdge<W> {
-    fn weight(&self) -> W { self.weight }
+    fn weight(&self) -> W { self.weight.clone() }
 }
 
 impl <W: Weight> FromStr for BasicEdge<W> {
-    type Err = String;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut rs = s.split(' ');
-        let u = rs.next()
-            .ok_or("Expected an unsigned integer here, but found nothing!")?
-            .parse()
-            .or(Err("Could not parse as an unsigned integer!"))?;
-        let v = rs.next()
-            .ok_or("Expected an unsigned integer here, but found nothing!")?
-            .parse()
-            .or(Err("Could not parse as an unsigned integer!"))?;
-        let w = W::from_str(rs.next().unwrap_or_else(|| "1")).unwrap_or_else(|_|1.into());
+        let u = parse_next(&mut rs, 1, "the base of the edge")?;
+        let v = parse_next(&mut rs, 1, "the tip of the edge")?;
+        let w = parse_weight(&mut rs, 1)?;
+        let l = parse_label(&mut rs, 1)?;
 
         Ok(BasicEdge {
             from: u,
             to: v,
             weight: w,
+            label: l,
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+use crate::structure::graph::edge::Edge;
+use crate::structure::graph::Graph;
+use crate::structure::weight::Weight;
+
+/**
+A set of vertices and edges that are closed for a query, such as links that are down for maintenance.
+
+An edge is banned by its endpoints, so every edge between the two is closed, just like UndirectedGraph::delete_edges.
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mask {
+    vertices: BTreeSet<usize>,
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl Mask {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn ban_vertex(mut self, u: usize) -> Self {
+        self.vertices.insert(u);
+        self
+    }
+
+    pub fn ban_edge(mut self, u: usize, v: usize) -> Self {
+        self.edges.insert((u.min(v), u.max(v)));
+        self
+    }
+
+    pub fn is_banned_vertex(&self, u: usize) -> bool {
+        self.vertices.contains(&u)
+    }
+
+    pub fn is_banned_edge(&self, u: usize, v: usize) -> bool {
+        self.edges.contains(&(u.min(v), u.max(v)))
+    }
+
+    // True if the edge is banned itself, or leads to or from a banned vertex.
+    pub fn hides(&self, u: usize, v: usize) -> bool {
+        self.is_banned_vertex(u) || self.is_banned_vertex(v) || self.is_banned_edge(u, v)
+    }
+}
+
+/**
+A graph with a mask over it, which every algorithm sees as if the banned vertices and edges were deleted, without copying the graph.
+A banned vertex keeps its number, but loses all its edges, so no path can go through it.
+*/
+pub struct MaskedGraph<'a, W: Weight, E: Edge<W>, G: Graph<W,E>> {
+    graph: &'a G,
+    mask: &'a Mask,
+    _marker: PhantomData<(W, E)>,
+}
+
+impl <'a, W: Weight, E: Edge<W>, G: Graph<W,E>> MaskedGraph<'a,W,E,G> {
+    pub fn new(graph: &'a G, mask: &'a Mask) -> Self {
+        MaskedGraph {
+            graph,
+            mask,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl <'a, W: Weight, E: Edge<W>, G: Graph<W,E>> Graph<W,E> for MaskedGraph<'a,W,E,G> {
+    fn n(&self) -> usize { self.graph.n() }
+    // Every edge is seen from both ends, self-loops included, so this counts each one twice.
+    fn m(&self) -> usize {
+        self.vertices().map(|u| self.neighbours(u).count()).sum::<usize>() / 2
+    }
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = E> + '_ {
+        self.graph.neighbours(u).filter(|e| ! self.mask.hides(e.from(), e.to()))
+    }
+}
+
+#[cfg(test)]
+mod test_masked_graph {
+    use std::fs::read_to_string;
+    use crate::algorithm::shortest_odd_path::shortest_odd_path;
+    use crate::algorithm::shortest_odd_walk::shortest_odd_walk;
+    use crate::algorithm::shortest_path::shortest_path;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+    use crate::structure::path_result::PathResult::{self, *};
+    use super::*;
+
+    fn cost<E: Edge<u64>>(result: PathResult<u64, E>) -> Option<u64> {
+        match result {
+            Possible {cost, path: _} => Some(cost),
+            Impossible => None,
+        }
+    }
+
+    #[test]
+    fn test_mask() {
+        let mask = Mask::new().ban_vertex(2).ban_edge(4, 1);
+        assert!(mask.is_banned_vertex(2) && ! mask.is_banned_vertex(1));
+        assert!(mask.is_banned_edge(1, 4) && mask.is_banned_edge(4, 1));
+        assert!(mask.hides(3, 2));
+        assert!(! mask.hides(3, 4));
+    }
+
+    // Masking must give the same answers as deleting the vertices and edges from a copy of the graph.
+    #[test]
+    fn test_same_answers() {
+        for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+            let graph: UndirectedGraph<u64, BasicEdge<u64>> = read_to_string(["data/small_graphs/", name, "/", name, ".in"].concat())
+                .unwrap()
+                .parse()
+                .unwrap();
+            let edges = graph.edges();
+            let (banned_vertex, banned_edge) = (graph.n() / 2, &edges[edges.len() / 2]);
+            let mask = Mask::new()
+                .ban_vertex(banned_vertex)
+                .ban_edge(banned_edge.from(), banned_edge.to());
+            let masked = MaskedGraph::new(&graph, &mask);
+
+            let mut deleted = graph.clone();
+            deleted.delete_edges(&vec![banned_edge.clone()]);
+            deleted.delete_edges(&graph[banned_vertex].clone());
+            assert_eq!(deleted.m(), masked.m());
+
+            for s in graph.vertices() {
+                for t in graph.vertices().filter(|&t| t != s) {
+                    assert_eq!(cost(shortest_path(&deleted, s, t)), cost(shortest_path(&masked, s, t)));
+                    assert_eq!(cost(shortest_odd_walk(&deleted, s, t)), cost(shortest_odd_walk(&masked, s, t)));
+                    assert_eq!(cost(shortest_odd_path(&deleted, s, t)), cost(shortest_odd_path(&masked, s, t)));
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,53 @@
 pub mod edge;
 pub mod undirected_graph;
+pub mod csr_graph;
+pub mod masked_graph;
 pub mod planar_graph;
 mod point;
+mod crossings;
 pub mod planar_edge;
 pub mod simple_graph_strategy;
+#[cfg(feature = "petgraph")]
+pub mod petgraph_graph;
+
+use crate::structure::graph::edge::Edge;
+use crate::structure::weight::Weight;
+
+/**
+The read-only view of an undirected graph that the algorithms need, so they can run on any graph storage without copying it first.
+The vertices are always 0..n, and every edge is seen from both endpoints: each edge out of u in neighbours(u) starts in u.
+*/
+pub trait Graph<W: Weight, E: Edge<W>> {
+    fn n(&self) -> usize;
+    fn m(&self) -> usize;
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = E> + '_;
+    fn is_adjacent(&self, u: usize, v: usize) -> bool {
+        self.neighbours(u).any(|e| e.to() == v)
+    }
+    fn vertices(&self) -> impl Iterator<Item = usize> {
+        0..self.n()
+    }
+}
+
+// The batch queries share graphs between threads, so every graph has to stay Send and Sync.
+#[cfg(test)]
+mod test_graph {
+    use crate::structure::graph::csr_graph::CsrGraph;
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::masked_graph::MaskedGraph;
+    use crate::structure::graph::planar_edge::PlanarEdge;
+    use crate::structure::graph::planar_graph::PlanarGraph;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<UndirectedGraph<u64, BasicEdge<u64>>>();
+        assert_send_sync::<UndirectedGraph<f64, PlanarEdge<f64>>>();
+        assert_send_sync::<CsrGraph<u64, BasicEdge<u64>>>();
+        assert_send_sync::<MaskedGraph<u64, BasicEdge<u64>, UndirectedGraph<u64, BasicEdge<u64>>>>();
+        assert_send_sync::<PlanarGraph<f64>>();
+        assert_send_sync::<PlanarGraph<f64, CsrGraph<f64, PlanarEdge<f64>>>>();
+    }
+}
@@ -0,0 +1,96 @@
+use petgraph::graph::{IndexType, NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+use crate::structure::graph::edge::BasicEdge;
+use crate::structure::graph::Graph;
+use crate::structure::weight::Weight;
+
+/**
+Lets the algorithms run directly on a petgraph UnGraph, with the node indices as vertices and the edge weights as costs.
+The node weights are ignored, and a self-loop is seen twice from its vertex, once from each end, just like in an UndirectedGraph.
+*/
+impl <N, W: Weight, Ix: IndexType> Graph<W, BasicEdge<W>> for UnGraph<N, W, Ix> {
+    fn n(&self) -> usize { self.node_count() }
+    fn m(&self) -> usize { self.edge_count() }
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = BasicEdge<W>> + '_ {
+        // petgraph only gives a self-loop once, so it is repeated for its other end.
+        self.edges(NodeIndex::new(u)).flat_map(move |e| {
+            let v = if e.source().index() == u { e.target() } else { e.source() };
+            let ends = if v.index() == u { 2 } else { 1 };
+            std::iter::repeat(BasicEdge::new(u, v.index(), e.weight().clone())).take(ends)
+        })
+    }
+    fn is_adjacent(&self, u: usize, v: usize) -> bool {
+        self.contains_edge(NodeIndex::new(u), NodeIndex::new(v))
+    }
+}
+
+#[cfg(test)]
+mod test_petgraph_graph {
+    use std::fs::read_to_string;
+    use petgraph::graph::UnGraph;
+    use crate::algorithm::shortest_odd_path::shortest_odd_path;
+    use crate::algorithm::shortest_path::{all_shortest_paths, bfs};
+    use crate::structure::graph::edge::{BasicEdge, Edge};
+    use crate::structure::graph::Graph;
+    use crate::structure::graph::masked_graph::{Mask, MaskedGraph};
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+    use crate::structure::weight::Weighted;
+    use crate::structure::path_result::PathResult::*;
+
+    fn to_petgraph(graph: &UndirectedGraph<u64, BasicEdge<u64>>) -> UnGraph<(), u64> {
+        let mut pet = UnGraph::with_capacity(graph.n(), graph.m());
+        for _ in graph.vertices() {
+            pet.add_node(());
+        }
+        for e in graph.edges().iter().filter(|e| e.from() < e.to()) {
+            pet.add_edge((e.from() as u32).into(), (e.to() as u32).into(), e.weight());
+        }
+        pet
+    }
+
+    #[test]
+    fn test_petgraph() {
+        let pet: UnGraph<(), u64> = UnGraph::from_edges([(0, 1, 3), (1, 2, 4), (2, 0, 5), (2, 3, 1)]);
+
+        assert_eq!(Graph::<u64, BasicEdge<u64>>::n(&pet), 4);
+        assert_eq!(Graph::<u64, BasicEdge<u64>>::m(&pet), 4);
+        assert!(Graph::<u64, BasicEdge<u64>>::is_adjacent(&pet, 3, 2));
+        assert!( ! Graph::<u64, BasicEdge<u64>>::is_adjacent(&pet, 3, 0));
+        let mut n2: Vec<BasicEdge<u64>> = pet.neighbours(2).collect();
+        n2.sort();
+        assert_eq!(n2, vec![BasicEdge::new(2, 0, 5), BasicEdge::new(2, 1, 4), BasicEdge::new(2, 3, 1)]);
+
+        match shortest_odd_path(&pet, 0, 3) {
+            Possible {cost, path} => {
+                assert_eq!(cost, 8);
+                assert_eq!(path, vec![BasicEdge::new(0, 1, 3), BasicEdge::new(1, 2, 4), BasicEdge::new(2, 3, 1)]);
+            }
+            Impossible => panic!("Expected an odd path from 0 to 3"),
+        }
+    }
+
+    // A self-loop has to be seen from both its ends, so that masking the graph still counts it as one edge.
+    #[test]
+    fn test_self_loop() {
+        let pet: UnGraph<(), u64> = UnGraph::from_edges([(0, 1, 3), (1, 1, 2)]);
+        let n1: Vec<BasicEdge<u64>> = pet.neighbours(1).collect();
+        assert_eq!(n1.iter().filter(|e| e.to() == 1).count(), 2);
+        assert_eq!(n1.len(), 3);
+
+        let mask = Mask::new();
+        assert_eq!(MaskedGraph::new(&pet, &mask).m(), Graph::<u64, BasicEdge<u64>>::m(&pet));
+    }
+
+    #[test]
+    fn test_same_answers() {
+        let graph: UndirectedGraph<u64, BasicEdge<u64>> = read_to_string("data/medium_graphs/medium1/medium1.in")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let pet = to_petgraph(&graph);
+        for s in graph.vertices() {
+            assert_eq!(bfs(&graph, s), bfs(&pet, s));
+            assert_eq!(all_shortest_paths(&graph, s), all_shortest_paths(&pet, s));
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::cmp::Ordering::{self, Equal};
 use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
-use crate::structure::graph::edge::{Edge};
+use crate::structure::error::{Error, parse_next};
+use crate::structure::graph::edge::{Edge, parse_weight};
 use crate::structure::graph::point::Point;
 use crate::structure::weight::{Weight, Weighted};
 
@@ -38,7 +39,7 @@ impl <W: Weight> PlanarEdge<W> {
             to: self.right,
             left: self.to,
             right: self.from,
-            weight: self.weight,
+            weight: self.weight.clone(),
         }
     }
 }
@@ -48,7 +49,7 @@ impl <W: Weight> PrePlanarEdge<W> {
         PlanarEdge {
             from: self.from,
             to: self.to,
-            weight: self.weight,
+            weight: self.weight.clone(),
             left: self.left.unwrap(),
             right: self.right.unwrap(),
         }
@@ -65,15 +66,17 @@ impl <W: Weight> PrePlanarEdge<W> {
 }
 
 pub use intersection::intersect;
+pub (in crate::structure::graph) use intersection::{orientation, Orientation};
 mod intersection {
-    use std::cmp::Ordering::{Equal, Greater, Less};
+    use std::cmp::Ordering::{self, Equal, Greater, Less};
+    use num::{BigRational, Zero};
     use crate::structure::graph::edge::Edge;
     use crate::structure::graph::point::Point;
     use crate::structure::weight::Weight;
     use Orientation::*;
 
     #[derive(PartialEq)]
-    enum Orientation {
+    pub enum Orientation {
         Clockwise,
         Counterclockwise,
         Colinear,
@@ -85,8 +88,11 @@ mod intersection {
         let c = &points[cd.from()];
         let d = &points[cd.to()];
 
-        if a == c || a == d || b == c || b == d {
-            return false
+        // Edges that share an endpoint only cross if they lie on top of each other.
+        for (p, q, r, s) in [(a, b, c, d), (a, b, d, c), (b, a, c, d), (b, a, d, c)] {
+            if p == r {
+                return orientation(p, q, s) == Colinear && same_direction(p, q, s);
+            }
         }
 
         let o1 = orientation(a, b, c);
@@ -100,15 +106,39 @@ mod intersection {
             o3 == Colinear && on_segment(c, a, d) ||
             o4 == Colinear && on_segment(c, b, d)
     }
-    fn orientation(p: &Point, q: &Point, r: &Point) -> Orientation {
-        let val = (q.y-p.y) * (r.x-q.x) - (q.x-p.x) * (r.y-q.y);
-        match val.total_cmp(&0.0) {
+    pub fn orientation(p: &Point, q: &Point, r: &Point) -> Orientation {
+        let (left, right) = ((q.y-p.y) * (r.x-q.x), (q.x-p.x) * (r.y-q.y));
+        let val = left - right;
+        // Rounding can only get the sign wrong this close to 0 (Shewchuk's bound for orient2d), so then it is computed exactly instead.
+        let eps = f64::EPSILON / 2.0;
+        let sign = if val.abs() > (3.0 + 16.0 * eps) * eps * (left.abs() + right.abs()) {
+            val.partial_cmp(&0.0).unwrap_or(Equal)
+        }
+        else {
+            exact_orientation(p, q, r).unwrap_or(Equal)
+        };
+        match sign {
             Greater => Clockwise,
             Less => Counterclockwise,
             Equal => Colinear,
         }
     }
 
+    // Every finite f64 is a fraction, so the sign can be computed without any rounding at all.
+    fn exact_orientation(p: &Point, q: &Point, r: &Point) -> Option<Ordering> {
+        let exact = BigRational::from_float::<f64>;
+        let (px, py, qx, qy, rx, ry) = (exact(p.x)?, exact(p.y)?, exact(q.x)?, exact(q.y)?, exact(r.x)?, exact(r.y)?);
+        let val = (&qy - py) * (rx - &qx) - (qx - px) * (ry - qy);
+        Some(val.cmp(&BigRational::zero()))
+    }
+
+    // Whether q and s lie in the same direction from p, if the three are on a line.
+    fn same_direction(p: &Point, q: &Point, s: &Point) -> bool {
+        q != p && s != p &&
+            q.x.partial_cmp(&p.x) == s.x.partial_cmp(&p.x) &&
+            q.y.partial_cmp(&p.y) == s.y.partial_cmp(&p.y)
+    }
+
     fn on_segment(p: &Point, q: &Point, r: &Point) -> bool {
         q.x <= p.x.max(r.x) &&
             q.x >= p.x.min(r.x) &&
@@ -118,7 +148,7 @@ mod intersection {
 }
 
 impl <W: Weight, S: Sealed> Weighted<W> for AbstractPlanarEdge<W,S> {
-    fn weight(&self) -> W { self.weight }
+    fn weight(&self) -> W { self.weight.clone() }
 }
 
 impl<W: Weight, S: Sealed> Debug for AbstractPlanarEdge<W,S> {
@@ -129,7 +159,7 @@ impl<W: Weight, S: Sealed> Debug for AbstractPlanarEdge<W,S> {
 
 impl<W: Weight, S: Sealed> PartialOrd for AbstractPlanarEdge<W,S> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        (self.from,self.to,&self.left,&self.right,self.weight).partial_cmp(&(other.from,other.to,&other.left,&other.right,other.weight))
+        (self.from,self.to,&self.left,&self.right,&self.weight).partial_cmp(&(other.from,other.to,&other.left,&other.right,&other.weight))
     }
 }
 
@@ -146,7 +176,7 @@ impl <W: Weight, S: Sealed> Edge<W> for AbstractPlanarEdge<W,S> {
         Self {
             from: self.to,
             to: self.from,
-            weight: self.weight,
+            weight: self.weight.clone(),
             left: self.right,
             right: self.left,
         }
@@ -157,14 +187,14 @@ impl <W: Weight, S: Sealed> Edge<W> for AbstractPlanarEdge<W,S> {
             Self {
                 from: self.from,
                 to: middle,
-                weight: self.weight,
+                weight: self.weight.clone(),
                 left: self.left,
                 right: self.right,
             },
             Self {
                 from: middle,
                 to: self.to,
-                weight: 0.into(),
+                weight: W::zero(),
                 left: self.left,
                 right: self.right,
             }
@@ -174,7 +204,7 @@ impl <W: Weight, S: Sealed> Edge<W> for AbstractPlanarEdge<W,S> {
         Self {
             from: (self.from as i64 + offset) as usize,
             to: (self.to as i64 + offset) as usize,
-            weight: self.weight,
+            weight: self.weight.clone(),
             left: self.left,
             right: self.right,
         }
@@ -184,19 +214,13 @@ impl <W: Weight, S: Sealed> Edge<W> for AbstractPlanarEdge<W,S> {
 impl <W: Weight, S: Sealed> Eq for AbstractPlanarEdge<W,S> {}
 
 impl <W: Weight, S: Sealed> FromStr for AbstractPlanarEdge<W,S> {
-    type Err = &'static str;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut rs = s.split(' ');
         Ok(Self {
-            from: rs.next()
-                .ok_or("Expected an unsigned integer here, but found nothing!")?
-                .parse()
-                .or(Err("Could not parse the base of the edge as an unsigned integer!"))?,
-            to: rs.next()
-                .ok_or("Expected an unsigned integer here, but found nothing!")?
-                .parse()
-                .or(Err("Could not parse the tip of the edge as an unsigned integer!"))?,
-            weight: W::from_str(rs.next().unwrap_or_else(|| "1")).unwrap_or_else(|_|1.into()),
+            from: parse_next(&mut rs, 1, "the base of the edge")?,
+            to: parse_next(&mut rs, 1, "the tip of the edge")?,
+            weight: parse_weight(&mut rs, 1)?,
             left: S::default(),
             right: S::default(),
         })
@@ -205,6 +229,7 @@ impl <W: Weight, S: Sealed> FromStr for AbstractPlanarEdge<W,S> {
 
 #[cfg(test)]
 mod test_intersection {
+    use crate::structure::graph::edge::Edge;
     use crate::structure::graph::planar_edge::{intersect, PrePlanarEdge};
     use crate::structure::graph::point::Point;
 
@@ -240,4 +265,38 @@ mod test_intersection {
 
         assert_intersect(&points, true, 2, 3);
     }
+
+    #[test]
+    fn test_touching_and_overlapping() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(3.0, 0.0)];
+        let edge = |u, v| PrePlanarEdge::<u64>::new(u, v, 0);
+        // Two edges from the same vertex that lie on top of each other.
+        assert!(intersect(&points, &edge(0, 2), &edge(0, 1)));
+        assert!(intersect(&points, &edge(1, 0), &edge(2, 0)));
+        // Edges that only meet in an endpoint, whether or not they continue in a straight line.
+        assert!(! intersect(&points, &edge(0, 2), &edge(2, 1)));
+        assert!(! intersect(&points, &edge(0, 1), &edge(1, 4)));
+        assert!(! intersect(&points, &edge(0, 2), &edge(2, 3)));
+        // A vertex in the middle of another edge, where the rounded cross product is -0.0.
+        assert!(intersect(&points, &edge(0, 1), &edge(2, 3)));
+        assert!(intersect(&points, &edge(3, 2), &edge(1, 0)));
+    }
+
+    #[test]
+    fn test_intersect_is_symmetric() {
+        // Points on the line y = 3x, give or take the rounding of their coordinates.
+        let points: Vec<Point> = (0..4)
+            .flat_map(|i| [Point::new(0.1 * i as f64, 0.3 * i as f64), Point::new(0.1 * i as f64, 0.6 * i as f64 / 2.0)])
+            .collect();
+        let edges: Vec<PrePlanarEdge<u64>> = (0..points.len())
+            .flat_map(|u| (0..points.len()).filter(move |&v| v != u).map(move |v| PrePlanarEdge::new(u, v, 0)))
+            .collect();
+        for ab in &edges {
+            for cd in &edges {
+                let expected = intersect(&points, ab, cd);
+                assert_eq!(expected, intersect(&points, cd, ab), "{:?} x {:?}", ab, cd);
+                assert_eq!(expected, intersect(&points, &ab.reverse(), cd), "{:?} x {:?}", ab, cd);
+            }
+        }
+    }
 }
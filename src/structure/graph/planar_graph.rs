@@ -1,55 +1,163 @@
 use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
 use std::str::FromStr;
+use crate::structure::error::{Error, Error::*, parse_next};
+use crate::structure::graph::csr_graph::CsrGraph;
 use crate::structure::graph::edge::Edge;
-use crate::structure::graph::planar_edge::{intersect, PlanarEdge, PrePlanarEdge};
+use crate::structure::graph::Graph;
+use crate::structure::graph::crossings::find_crossings;
+use crate::structure::graph::planar_edge::{PlanarEdge, PrePlanarEdge};
 use crate::structure::graph::point::{compare_edges_clockwise, Point};
 use crate::structure::graph::simple_graph_strategy::{SimpleGraphStrategy, SumWeights};
 use crate::structure::graph::undirected_graph::UndirectedGraph;
-use crate::structure::weight::Weight;
-use crate::utility::misc::{debug, repeat};
+use crate::structure::weight::{check_weight, Weight};
+use crate::utility::misc::repeat;
+use crate::utility::logging::{debug, warn};
+
+// How many pairs of crossing edges to look for when the embedding is not planar, to give as examples in the error.
+pub const MAX_CROSSINGS: usize = 10;
 
 #[derive(Clone)]
-pub struct PlanarGraph<W: Weight> {
-    real: UndirectedGraph<W, PlanarEdge<W>>,
-    dual: UndirectedGraph<W, PlanarEdge<W>>,
+pub struct PlanarGraph<W: Weight, G: Graph<W, PlanarEdge<W>> = UndirectedGraph<W, PlanarEdge<W>>> {
+    real: G,
+    dual: G,
+    _marker: PhantomData<W>,
 }
 
-impl <W: Weight> PlanarGraph<W> {
-    pub fn real(&self) -> &UndirectedGraph<W, PlanarEdge<W>> { &self.real }
-    pub fn dual(&self) -> &UndirectedGraph<W, PlanarEdge<W>> { &self.dual }
+impl <W: Weight, G: Graph<W, PlanarEdge<W>>> PlanarGraph<W,G> {
+    pub fn real(&self) -> &G { &self.real }
+    pub fn dual(&self) -> &G { &self.dual }
     pub fn n(&self) -> usize { self.real.n() }
     pub fn m(&self) -> usize { self.real.m() }
     pub fn f(&self) -> usize { self.dual.n() }
-    pub fn parse<S: SimpleGraphStrategy>(str: &str, assert_planarity: bool) -> Result<Self, &'static str> {
-        let mut ls = str.lines()
-            .map(str::trim)
-            .filter(|&l| l.len() > 0 && ! l.starts_with("%"));
-        let mut row1 = ls.next()
-            .ok_or("Could not find the first row")?
-            .split(' ')
-            .map(usize::from_str);
-        let n = row1.next()
-            .ok_or("Could not find n")?
-            .or(Err("Could not parse n"))?;
-        let m = row1.next()
-            .ok_or("Could not find m")?
-            .or(Err("Could not parse m"))?;
+}
+
+impl <W: Weight> PlanarGraph<W> {
+    /**
+    Parse a planar graph with coordinates for every vertex.
+    Edges of weight zero are rejected, and so are negative, infinite or NaN weights.
+    */
+    pub fn parse<S: SimpleGraphStrategy>(str: &str, assert_planarity: bool) -> Result<Self, Error> {
+        Self::parse_coordinates::<S>(str, assert_planarity, false)
+    }
+
+    /**
+    The same as parse, but allow edges of weight zero.
+    */
+    pub fn parse_with_zero_weights<S: SimpleGraphStrategy>(str: &str, assert_planarity: bool) -> Result<Self, Error> {
+        Self::parse_coordinates::<S>(str, assert_planarity, true)
+    }
+
+    fn parse_coordinates<S: SimpleGraphStrategy>(str: &str, assert_planarity: bool, zero_weights: bool) -> Result<Self, Error> {
+        let mut ls = lines(str);
+        let (n, m) = parse_header(&mut ls)?;
         let mut pre = PrePlanarGraph::empty(n, assert_planarity);
 
         for _ in 0..n {
-            let mut ws = ls.next().ok_or("Expected another vertex here, but got nothing")?.split(' ');
-            let id = ws.next().ok_or("Could not find the id")?.parse().or(Err("Could not parse the id"))?;
-            let x = ws.next().ok_or("Could not find the x coordinate")?.parse().or(Err("Could not parse the x coordinate"))?;
-            let y = ws.next().ok_or("Could not find the y coordinate")?.parse().or(Err("Could not parse the y coordinate"))?;
+            let (line, row) = ls.next().ok_or(UnexpectedEnd {expected: "another vertex"})?;
+            let mut ws = row.split(' ');
+            let id = parse_next(&mut ws, line, "the id of the vertex")?;
+            let x = parse_next(&mut ws, line, "the x coordinate")?;
+            let y = parse_next(&mut ws, line, "the y coordinate")?;
+            if id >= n {
+                return Err(UnknownVertex {line, vertex: id, n});
+            }
             pre.add_vertex(id, Point::new(x, y));
         }
-        for _ in 0..m {
-            pre.add_edge::<S>(ls.next().ok_or("Expected another edge here, but got nothing")?.parse()?);
+        parse_edges::<W,S>(&mut ls, &mut pre, m, zero_weights)?;
+        pre.planarize()
+    }
+
+    /**
+    Parse a planar graph given by a rotation system instead of coordinates.
+    The first line holds n and m, then follows one line per vertex with its id and its neighbours in clockwise order,
+    and then one line per edge, just like in `parse`.
+    */
+    pub fn parse_rotation_system<S: SimpleGraphStrategy>(str: &str) -> Result<Self, Error> {
+        Self::parse_rotations::<S>(str, false)
+    }
+
+    /**
+    The same as parse_rotation_system, but allow edges of weight zero.
+    */
+    pub fn parse_rotation_system_with_zero_weights<S: SimpleGraphStrategy>(str: &str) -> Result<Self, Error> {
+        Self::parse_rotations::<S>(str, true)
+    }
+
+    fn parse_rotations<S: SimpleGraphStrategy>(str: &str, zero_weights: bool) -> Result<Self, Error> {
+        let mut ls = lines(str);
+        let (n, m) = parse_header(&mut ls)?;
+        let mut pre = PrePlanarGraph::empty(n, true);
+        let mut rotation = repeat(n, Vec::new());
+
+        for _ in 0..n {
+            let (line, row) = ls.next().ok_or(UnexpectedEnd {expected: "another vertex"})?;
+            let mut ws = row.split(' ');
+            let id = parse_next(&mut ws, line, "the id of the vertex")?;
+            if id >= n {
+                return Err(UnknownVertex {line, vertex: id, n});
+            }
+            for w in ws {
+                let v = w.parse().or(Err(InvalidValue {line, expected: "a neighbour in the rotation", found: w.to_string()}))?;
+                if v >= n {
+                    return Err(UnknownVertex {line, vertex: v, n});
+                }
+                rotation[id].push(v);
+            }
+        }
+        parse_edges::<W,S>(&mut ls, &mut pre, m, zero_weights)?;
+        pre.embed_by_rotation(&rotation)
+    }
+
+    /**
+    Build a planar graph from a combinatorial embedding: the neighbours of every vertex u in clockwise order around u.
+    Parallel edges are combined with the strategy S, and the rotation of each vertex must list each of its neighbours exactly once, so self-loops are not allowed.
+    The faces are traced out from the rotation, and it is rejected if they do not match Euler's formula.
+    */
+    pub fn from_rotation_system<S: SimpleGraphStrategy, E: Edge<W>>(graph: &UndirectedGraph<W,E>, rotation: &[Vec<usize>]) -> Result<Self, Error> {
+        let mut pre = PrePlanarGraph::empty(graph.n(), true);
+        for u in graph.vertices() {
+            for e in graph[u].iter().filter(|e| e.from() <= e.to()) {
+                pre.add_edge::<S>(PrePlanarEdge::new(e.from(), e.to(), e.weight()));
+            }
         }
-        Ok(pre.planarize()?)
+        pre.embed_by_rotation(rotation)
     }
 }
 
+fn lines(str: &str) -> impl Iterator<Item = (usize, &str)> {
+    str.lines()
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .filter(|&(_, l)| l.len() > 0 && ! l.starts_with("%"))
+}
+
+fn parse_header<'a>(ls: &mut impl Iterator<Item = (usize, &'a str)>) -> Result<(usize, usize), Error> {
+    let (line, row1) = ls.next().ok_or(UnexpectedEnd {expected: "the number of vertices and edges"})?;
+    let mut row1 = row1.split(' ');
+    let n = parse_next(&mut row1, line, "the number of vertices")?;
+    let m = parse_next(&mut row1, line, "the number of edges")?;
+    Ok((n, m))
+}
+
+fn parse_edges<'a, W: Weight, S: SimpleGraphStrategy>(ls: &mut impl Iterator<Item = (usize, &'a str)>, pre: &mut PrePlanarGraph<W>, m: usize, zero_weights: bool) -> Result<(), Error> {
+    let n = pre.graph.n();
+    for _ in 0..m {
+        let (line, row) = ls.next().ok_or(UnexpectedEnd {expected: "another edge"})?;
+        let e: PrePlanarEdge<W> = row.parse().map_err(|err: Error| err.on_line(line))?;
+        for vertex in [e.from(), e.to()] {
+            if vertex >= n {
+                return Err(UnknownVertex {line, vertex, n});
+            }
+        }
+        check_weight(&e.weight, zero_weights)
+            .map_err(|reason| InvalidWeight {line, weight: e.weight.to_string(), reason})?;
+        pre.add_edge::<S>(e);
+    }
+    Ok(())
+}
+
 struct PrePlanarGraph<W: Weight> {
     graph: UndirectedGraph<W, PrePlanarEdge<W>>,
     points: Vec<Option<Point>>,
@@ -59,7 +167,8 @@ struct PrePlanarGraph<W: Weight> {
 impl <W: Weight> PrePlanarGraph<W> {
     pub fn empty(n: usize, assert_planarity: bool) -> Self {
         PrePlanarGraph {
-            graph: UndirectedGraph::new(n),
+            // The weights are checked as they are parsed, or already were in the graph they are copied from.
+            graph: UndirectedGraph::new(n).allow_zero_weights(),
             points: repeat(n, None),
             assert_planarity,
         }
@@ -87,20 +196,30 @@ impl <W: Weight> PrePlanarGraph<W> {
         }
     }
 
-    pub fn planarize(mut self) -> Result<PlanarGraph<W>, &'static str> {
+    pub fn planarize(mut self) -> Result<PlanarGraph<W>, Error> {
         let mut points = Vec::new();
-        for p in &self.points {
-            points.push(p.clone().ok_or("Not all points have been defined")?);
+        for (vertex, p) in self.points.iter().enumerate() {
+            points.push(p.clone().ok_or(UndefinedVertex {vertex})?);
         }
         if self.assert_planarity {
             self.assert_planarity(&points)?;
         }
 
         self.sort_edges(&points);
+        self.embed()
+    }
+
+    pub fn embed_by_rotation(mut self, rotation: &[Vec<usize>]) -> Result<PlanarGraph<W>, Error> {
+        self.sort_edges_by_rotation(rotation)?;
+        self.embed()
+    }
+
+    // Find the faces and build the dual graph, assuming that the edges around each vertex are already sorted clockwise.
+    fn embed(mut self) -> Result<PlanarGraph<W>, Error> {
         let f = self.determine_faces()?;
 
-        let mut real = UndirectedGraph::new(self.graph.n());
-        let mut dual = UndirectedGraph::new(f);
+        let mut real = UndirectedGraph::new(self.graph.n()).allow_zero_weights();
+        let mut dual = UndirectedGraph::new(f).allow_zero_weights();
         self.graph.adj_list.iter().for_each(|xs| {
             xs.iter()
                 .filter(|e| e.from() < e.to())
@@ -115,6 +234,7 @@ impl <W: Weight> PrePlanarGraph<W> {
         Ok(PlanarGraph {
             real,
             dual,
+            _marker: PhantomData,
         })
     }
 
@@ -125,13 +245,36 @@ impl <W: Weight> PrePlanarGraph<W> {
                 .sort_by(compare_edges_clockwise(&points[u], &points));
         }
     }
-    fn determine_faces(&mut self) -> Result<usize, &'static str> {
+    fn sort_edges_by_rotation(&mut self, rotation: &[Vec<usize>]) -> Result<(), Error> {
+        let n = self.graph.n();
+        let mut position: Vec<Option<usize>> = repeat(n, None);
+        for u in 0..n {
+            let order = rotation.get(u).map(Vec::as_slice).unwrap_or(&[]);
+            let adj = &mut self.graph.adj_list[u];
+            let valid = order.len() == adj.len()
+                && order.iter().enumerate().all(|(i, &v)| v < n && position[v].replace(i).is_none())
+                && adj.iter().all(|e| position[e.to()].is_some());
+            if valid {
+                adj.sort_by_key(|e| position[e.to()]);
+            }
+            for &v in order.iter().filter(|&&v| v < n) {
+                position[v] = None;
+            }
+            if ! valid {
+                return Err(InvalidRotation {vertex: u});
+            }
+        }
+        Ok(())
+    }
+
+    fn determine_faces(&mut self) -> Result<usize, Error> {
         let n = self.graph.n();
         let adj_list = &mut self.graph.adj_list;
         let adj_list_copy = adj_list.clone();
         let mut current_face = 0;
         for start_vertex in 0..n {
-            for mut curr_line_id in 0..adj_list[start_vertex].len() {
+            for start_line_id in 0..adj_list[start_vertex].len() {
+                let mut curr_line_id = start_line_id;
                 let mut curr_line = &adj_list_copy[start_vertex][curr_line_id];
                 if adj_list[start_vertex][curr_line_id].left.is_none() {
                     loop {
@@ -144,7 +287,8 @@ impl <W: Weight> PrePlanarGraph<W> {
                         curr_line_id = (id + 1) % adj_list[curr_line.to].len();
                         curr_line = &adj_list_copy[curr_line.to][curr_line_id];
 
-                        if curr_line.from == start_vertex {
+                        // A face may pass through the same vertex several times, so we are only done once we are back at the first edge.
+                        if curr_line.from == start_vertex && curr_line_id == start_line_id {
                             break;
                         }
                     }
@@ -156,49 +300,55 @@ impl <W: Weight> PrePlanarGraph<W> {
         for u in 0..n {
             for e in &adj_list[u] {
                 if e.left.is_none() || e.right.is_none() {
-                    return Err("Not all edges found both a left and right region!");
+                    return Err(IncompleteFaces {from: e.from(), to: e.to()});
                 }
             }
         }
         if self.graph.m() > n + current_face || n + current_face - self.graph.m() != 2 {
-            debug(format!("n = {}, m = {}, f = {}", self.graph.n(), self.graph.m(), current_face));
-            debug(format!("We should have had {} - {} + 2 = {} regions, but we found {}.", self.graph.m(), n, self.graph.m() - self.graph.n() + 2, current_face));
-            debug(format!("Either we don't have the correct faces, or Euler's formula is wrong :thinkin:"));
+            debug!("n = {}, m = {}, f = {}", self.graph.n(), self.graph.m(), current_face);
+            warn!("We should have had {} - {} + 2 = {} regions, but we found {}.", self.graph.m(), n, (self.graph.m() + 2).saturating_sub(n), current_face);
+            debug!("Either we don't have the correct faces, or Euler's formula is wrong :thinkin:");
             if self.assert_planarity {
-                panic!("Incorrect number of regions compared to vertices and edges!");
+                return Err(EulerMismatch {n, m: self.graph.m(), f: current_face});
             }
         }
         Ok(current_face)
     }
 
-    fn assert_planarity(&self, points: &Vec<Point>) -> Result<(), &'static str> {
-        let mut errors = 0;
-        let edges = self.graph.edges();
-        for i in 0..edges.len() {
-            let ab = &edges[i];
-            if ab.from() > ab.to() { continue; }
-            for j in i+1..edges.len() {
-                let cd = &edges[j];
-                if cd.from() < cd.to() && ab != &cd.reverse() && intersect(&points, ab, cd) {
-                    if errors == 0 {
-                        debug("    This cannot be a straight-line embedding, here are some pairs of edges that intersect: ".to_string());
-                    }
-                    if errors < 10 {
-                        debug(format!("        {}  x  {}", ab.format_with_coords(&points), cd.format_with_coords(&points)));
-                    }
-                    errors += 1;
-                }
-            }
+    fn assert_planarity(&self, points: &Vec<Point>) -> Result<(), Error> {
+        let edges: Vec<PrePlanarEdge<W>> = self.graph.edges()
+            .into_iter()
+            .filter(|e| e.from() < e.to())
+            .collect();
+        let crossings = find_crossings(points, &edges, MAX_CROSSINGS);
+        if crossings.is_empty() {
+            return Ok(());
         }
-        if errors == 0 {
-            Ok(())
-        }else {
-            Err("This is not a straight-line embedding :(")
+
+        debug!("    This cannot be a straight-line embedding, here are some pairs of edges that intersect: ");
+        let examples = crossings.iter()
+            .map(|&(i, j)| {
+                let (ab, cd) = (&edges[i], &edges[j]);
+                debug!("        {}  x  {}", ab.format_with_coords(points), cd.format_with_coords(points));
+                ((ab.from(), ab.to()), (cd.from(), cd.to()))
+            })
+            .collect();
+        Err(CrossingEdges {crossings: crossings.len(), examples})
+    }
+}
+
+// Store both the real and the dual graph in compressed sparse row form.
+impl <W: Weight> From<&PlanarGraph<W>> for PlanarGraph<W, CsrGraph<W, PlanarEdge<W>>> {
+    fn from(planar: &PlanarGraph<W>) -> Self {
+        PlanarGraph {
+            real: CsrGraph::from(planar.real()),
+            dual: CsrGraph::from(planar.dual()),
+            _marker: PhantomData,
         }
     }
 }
 
-impl <W: Weight> Debug for PlanarGraph<W> {
+impl <W: Weight, G: Graph<W, PlanarEdge<W>> + Debug> Debug for PlanarGraph<W,G> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "PlanarGraph(n = {}, m = {}, f = {}):\n", self.n(), self.m(), self.f())?;
         write!(f, "Real part:\n")?;
@@ -210,20 +360,24 @@ impl <W: Weight> Debug for PlanarGraph<W> {
 }
 
 impl <W: Weight> FromStr for PlanarGraph<W> {
-    type Err = &'static str;
+    type Err = Error;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         Self::parse::<SumWeights>(str, false)
     }
 }
 
+#[cfg(test)]
 mod test_planar_graph {
     use std::fs::read_to_string;
+    use crate::structure::error::Error::*;
+    use crate::structure::graph::edge::BasicEdge;
     use crate::structure::graph::planar_graph::PlanarGraph;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
     use crate::structure::graph::simple_graph_strategy::SumWeights;
-    use crate::utility::misc::debug;
+    use crate::utility::logging::debug;
 
     fn parse(folder: &str, name: &str) -> PlanarGraph<f64> {
-        debug(format!("Attempting to parse {}...", name));
+        debug!("Attempting to parse {}...", name);
         let input = read_to_string(["data/planar_graphs/", folder, "/", name, "/", name, ".in"].concat())
             .expect("No graph found");
         PlanarGraph::parse::<SumWeights>(&input, true)
@@ -281,4 +435,49 @@ mod test_planar_graph {
             parse("small_planar_graphs", name);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rotation_system() {
+        for name in [
+            "small_planar1",
+            "small_planar2",
+            "small_planar3",
+            "small_planar4",
+            "small_planar5",
+        ] {
+            let planar = parse("small_planar_graphs", name);
+            let input = read_to_string(["data/planar_graphs/small_planar_graphs/", name, "/", name, ".rot"].concat())
+                .expect("No rotation system found");
+            let rotated = PlanarGraph::<f64>::parse_rotation_system::<SumWeights>(&input)
+                .unwrap_or_else(|err| panic!("Could not parse the rotation system of {}: {}", name, err));
+            assert_eq!(planar.real(), rotated.real(), "{}", name);
+            assert_eq!(planar.dual(), rotated.dual(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_k4_rotation() {
+        let k4: UndirectedGraph<u64, BasicEdge<u64>> = "4\n0 1\n0 2\n0 3\n1 2\n1 3\n2 3".parse().unwrap();
+        let planar = PlanarGraph::from_rotation_system::<SumWeights, _>(&k4, &[vec![1, 3, 2], vec![2, 3, 0], vec![0, 3, 1], vec![0, 1, 2]])
+            .unwrap();
+        assert_eq!(planar.f(), 4);
+        assert_eq!(planar.dual().m(), 6);
+
+        let twisted = PlanarGraph::from_rotation_system::<SumWeights, _>(&k4, &[vec![1, 2, 3], vec![2, 3, 0], vec![0, 3, 1], vec![0, 1, 2]]);
+        assert_eq!(twisted.err(), Some(EulerMismatch {n: 4, m: 6, f: 2}));
+
+        let missing = PlanarGraph::from_rotation_system::<SumWeights, _>(&k4, &[vec![1, 3], vec![2, 3, 0], vec![0, 3, 1], vec![0, 1, 2]]);
+        assert_eq!(missing.err(), Some(InvalidRotation {vertex: 0}));
+        let repeated = PlanarGraph::from_rotation_system::<SumWeights, _>(&k4, &[vec![1, 3, 2], vec![2, 3, 0], vec![0, 3, 0], vec![0, 1, 2]]);
+        assert_eq!(repeated.err(), Some(InvalidRotation {vertex: 2}));
+    }
+
+    #[test]
+    fn test_bowtie_rotation() {
+        // Two triangles sharing the vertex 0, so the outer face passes through 0 twice.
+        let bowtie: UndirectedGraph<u64, BasicEdge<u64>> = "5\n0 1\n0 2\n1 2\n0 3\n0 4\n3 4".parse().unwrap();
+        let planar = PlanarGraph::from_rotation_system::<SumWeights, _>(&bowtie, &[vec![1, 2, 3, 4], vec![2, 0], vec![0, 1], vec![4, 0], vec![0, 3]])
+            .unwrap();
+        assert_eq!(planar.f(), 3);
+    }
+}
@@ -3,8 +3,10 @@ use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 use std::str;
 use std::str::FromStr;
+use crate::structure::error::{Error, Error::*, parse_next};
 use crate::structure::graph::edge::Edge;
-use crate::structure::weight::Weight;
+use crate::structure::graph::Graph;
+use crate::structure::weight::{check_weight, Weight};
 
 #[derive(PartialEq, Clone)]
 pub struct UndirectedGraph<W, E>
@@ -14,6 +16,7 @@ pub struct UndirectedGraph<W, E>
     pub (in crate::structure::graph) adj_list: Vec<Vec<E>>,
     n: usize,
     m: usize,
+    zero_weights: bool,
     _marker: PhantomData<W>,
 }
 impl <W: Weight, E: Edge<W>> UndirectedGraph<W,E> {
@@ -22,16 +25,66 @@ impl <W: Weight, E: Edge<W>> UndirectedGraph<W,E> {
             adj_list: (0..n).map(|_| Vec::new()).collect(),
             n,
             m: 0,
+            zero_weights: false,
             _marker: PhantomData::default(),
         }
     }
+    /**
+    Allow edges that cost nothing, which add_edge rejects by default.
+    */
+    pub fn allow_zero_weights(mut self) -> Self {
+        self.zero_weights = true;
+        self
+    }
+    /**
+    Parse a graph in the same format as from_str, but allow edges of weight zero.
+    */
+    pub fn parse_with_zero_weights(str: &str) -> Result<Self, Error> {
+        Self::parse(str, true)
+    }
+    fn parse(str: &str, zero_weights: bool) -> Result<Self, Error> {
+        let (n, edges) = parse_edges(str, zero_weights)?;
+        let mut ret = UndirectedGraph::new(n);
+        ret.zero_weights = zero_weights;
+        for e in edges {
+            ret.add_edge(e);
+        }
+        Ok(ret)
+    }
     pub fn n(&self) -> usize { self.n }
     pub fn m(&self) -> usize { self.m }
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj_list.push(Vec::new());
+        self.n += 1;
+        self.n - 1
+    }
+    /**
+    Add an undirected edge to the graph.
+    Panics if an endpoint is not in the graph, or if try_add_edge would reject the weight.
+    */
     pub fn add_edge(&mut self, e: E) {
+        let (from, to) = (e.from(), e.to());
+        if let Err(err) = self.try_add_edge(e) {
+            panic!("Could not add the edge ({}, {}): {}", from, to, err);
+        }
+    }
+    /**
+    Add an undirected edge to the graph, or fail if an endpoint is not in the graph,
+    or if the weight is negative, infinite or not a number, or zero without allow_zero_weights.
+    */
+    pub fn try_add_edge(&mut self, e: E) -> Result<(), Error> {
+        for vertex in [e.from(), e.to()] {
+            if vertex >= self.n {
+                return Err(VertexOutOfRange {vertex, n: self.n});
+            }
+        }
+        check_weight(&e.weight(), self.zero_weights)
+            .map_err(|reason| UnusableWeight {weight: e.weight().to_string(), reason})?;
         let b = e.reverse();
         self.adj_list[e.from()].push(e);
         self.adj_list[b.from()].push(b);
         self.m += 1;
+        Ok(())
     }
     pub fn is_adjacent(&self, u: usize, v: usize) -> bool {
         let (p, q) = if self.adj_list[u].len() < self.adj_list[v].len() {
@@ -70,29 +123,51 @@ impl <W: Weight, E: Edge<W>> UndirectedGraph<W,E> {
 impl <W: Weight, E: Edge<W>> From<String> for UndirectedGraph<W,E> {
     fn from(value: String) -> Self {
         Self::from_str(value.as_str())
-            .expect(format!(
-                "Could not parse the following as an UndirectedGraph: \n{}",
+            .unwrap_or_else(|err| panic!(
+                "Could not parse the following as an UndirectedGraph ({}): \n{}",
+                err,
                 &value[..50.min(value.len()-1)]
-            ).as_str())
+            ))
     }
 }
 
 impl <W: Weight, E: Edge<W>> FromStr for UndirectedGraph<W,E> {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let mut ls = str.lines()
-            .map(str::trim)
-            .filter(|&l| l.len() > 0 && ! l.starts_with("%"));
-        let row1 = ls.next().ok_or("Expected an integer denoting the number of vertices, but found nothing!".to_owned())?;
-        let n = row1.split(' ').next().unwrap().parse().or(Err(format!("Could not parse '{}' as n", row1)))?;
-        let mut ret = UndirectedGraph::new(n);
-        for row in ls {
-            let p: E = row.parse().or_else(|_| Err(format!("Could not parse the row: {}", row)))?;
-            ret.add_edge(p);
+        Self::parse(str, false)
+    }
+}
+
+// Parse the number of vertices, and then one edge per line.
+pub (in crate::structure::graph) fn parse_edges<W: Weight, E: Edge<W>>(str: &str, zero_weights: bool) -> Result<(usize, Vec<E>), Error> {
+    let mut ls = str.lines()
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .filter(|&(_, l)| l.len() > 0 && ! l.starts_with("%"));
+    let (line, row1) = ls.next().ok_or(UnexpectedEnd {expected: "the number of vertices"})?;
+    let n = parse_next(&mut row1.split(' '), line, "the number of vertices")?;
+    let mut edges = Vec::new();
+    for (line, row) in ls {
+        let e: E = row.parse().map_err(|err: Error| err.on_line(line))?;
+        for vertex in [e.from(), e.to()] {
+            if vertex >= n {
+                return Err(UnknownVertex {line, vertex, n});
+            }
         }
-        Ok(ret)
+        check_weight(&e.weight(), zero_weights)
+            .map_err(|reason| InvalidWeight {line, weight: e.weight().to_string(), reason})?;
+        edges.push(e);
     }
+    Ok((n, edges))
+}
+
+impl <W: Weight, E: Edge<W>> Graph<W,E> for UndirectedGraph<W,E> {
+    fn n(&self) -> usize { self.n }
+    fn m(&self) -> usize { self.m }
+    fn neighbours(&self, u: usize) -> impl Iterator<Item = E> + '_ { self.adj_list[u].iter().cloned() }
+    fn is_adjacent(&self, u: usize, v: usize) -> bool { self.is_adjacent(u, v) }
 }
 
 impl <W: Weight, E: Edge<W>> Index<&usize> for UndirectedGraph<W,E> {
@@ -132,7 +207,10 @@ impl <W:Weight,E:Edge<W>> Display for UndirectedGraph<W,E> {
         for u in self.vertices() {
             for e in &self[u] {
                 if u < e.to() {
-                    if e.weight() != 1.into() {
+                    if e.label() {
+                        ret.push_str(format!("{} {} {} 1\n", u, e.to(), e.weight()).as_str())
+                    }
+                    else if e.weight() != W::one() {
                         ret.push_str(format!("{} {} {}\n", u, e.to(), e.weight()).as_str())
                     }
                     else {
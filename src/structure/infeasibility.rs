@@ -0,0 +1,16 @@
+/**
+Evidence that there is no odd path between two vertices s and t.
+
+Disconnected holds the component of s, which does not contain t.
+EvenBlocks colours every vertex that lies on some s-t-path, such that no edge between them has the same colour at both ends, but s and t do.
+The rest of the graph only touches them in one vertex per component, so no path can leave them and come back, and every s-t-path is even.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Infeasibility {
+    Disconnected {
+        component: Vec<usize>,
+    },
+    EvenBlocks {
+        colouring: Vec<Option<bool>>,
+    },
+}
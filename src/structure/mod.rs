@@ -1,6 +1,13 @@
 pub mod cost;
 pub mod path_result;
+pub mod planarity;
+pub mod bipartition;
+pub mod infeasibility;
+pub mod event;
+pub mod error;
 pub mod weight;
+pub mod checked;
 pub mod todo;
 pub mod graph;
 pub mod basis;
+pub mod stamped;
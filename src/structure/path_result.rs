@@ -1,6 +1,7 @@
 use crate::structure::graph::edge::Edge;
 use crate::structure::weight::Weight;
 
+#[derive(Clone)]
 pub enum PathResult<W: Weight, E: Edge<W>> {
     Possible {
         cost: W,
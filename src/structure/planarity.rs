@@ -0,0 +1,8 @@
+pub enum Planarity<E> {
+    Planar {
+        rotation: Vec<Vec<usize>>,
+    },
+    NonPlanar {
+        kuratowski: Vec<E>,
+    },
+}
@@ -0,0 +1,81 @@
+use std::ops::{Index, IndexMut};
+
+/**
+A vector that can be reset to its default value in constant time, for state that is used again and again by many searches.
+
+Every entry remembers when it was last written. Entries written before the latest reset are read as the default,
+and are only overwritten once they are written to again.
+*/
+#[derive(Clone)]
+pub struct Stamped<T: Clone> {
+    values: Vec<T>,
+    stamps: Vec<u32>,
+    now: u32,
+    default: T,
+}
+
+impl <T: Clone> Stamped<T> {
+    pub fn new(n: usize, default: T) -> Self {
+        Stamped {
+            values: vec![default.clone(); n],
+            stamps: vec![0; n],
+            now: 0,
+            default,
+        }
+    }
+
+    pub fn len(&self) -> usize { self.values.len() }
+
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    pub fn reset(&mut self) {
+        self.now = self.now.wrapping_add(1);
+        // Once the clock wraps around, some old stamps would look fresh again, so they are all cleared instead.
+        if self.now == 0 {
+            self.values.fill(self.default.clone());
+            self.stamps.fill(0);
+        }
+    }
+}
+
+impl <T: Clone> Index<usize> for Stamped<T> {
+    type Output = T;
+    fn index(&self, u: usize) -> &Self::Output {
+        if self.stamps[u] == self.now { &self.values[u] } else { &self.default }
+    }
+}
+
+impl <T: Clone> IndexMut<usize> for Stamped<T> {
+    fn index_mut(&mut self, u: usize) -> &mut Self::Output {
+        if self.stamps[u] != self.now {
+            self.stamps[u] = self.now;
+            self.values[u] = self.default.clone();
+        }
+        &mut self.values[u]
+    }
+}
+
+#[cfg(test)]
+mod test_stamped {
+    use crate::structure::stamped::Stamped;
+
+    #[test]
+    fn test_stamped() {
+        let mut xs = Stamped::new(4, 0);
+        xs[1] = 5;
+        xs[2] += 3;
+        assert_eq!((0, 5, 3), (xs[0], xs[1], xs[2]));
+
+        xs.reset();
+        assert_eq!((0, 0, 0), (xs[0], xs[1], xs[2]));
+        xs[2] += 1;
+        assert_eq!(1, xs[2]);
+
+        // Wind the clock forward until it wraps around, which clears every entry at once.
+        xs[3] = 7;
+        xs.now = u32::MAX;
+        xs.reset();
+        assert_eq!(0, xs[3]);
+        assert_eq!(4, xs.len());
+    }
+}
@@ -1,5 +1,5 @@
 use std::cmp::Ordering;
-use std::cmp::Ordering::{Equal};
+use std::cmp::Ordering::{Equal, Greater};
 use crate::structure::weight::Weight;
 use Todo::*;
 use crate::structure::graph::edge::Edge;
@@ -14,8 +14,10 @@ impl <W: Weight, E: Edge<W>> PartialOrd for Todo<W,E> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
             (Vertex(w1, _),Vertex(w2, _)) => w1.partial_cmp(w2),
-            (Vertex(w1,_),Blossom(w2,_)) => (*w1+*w1, false).partial_cmp(&(*w2,true)),
-            (Blossom(w1,_),Vertex(w2,_)) => (*w1,true).partial_cmp(&(*w2+*w2,false)),
+            // A vertex at w1 goes before a blossom at w2 if 2*w1 <= w2, but w1 is compared with w2 - w1 instead, since doubling it could overflow.
+            (Vertex(w1,_),Blossom(w2,_)) if w2 < w1 => Some(Greater),
+            (Vertex(w1,_),Blossom(w2,_)) => (w1.clone(), false).partial_cmp(&(w2.clone() - w1.clone(), true)),
+            (Blossom(_,_),Vertex(_,_)) => other.partial_cmp(self).map(Ordering::reverse),
             (Blossom(w1,_),Blossom(w2,_)) => w1.partial_cmp(w2),
         }
     }
@@ -44,6 +46,8 @@ mod test_todo {
     use crate::structure::graph::edge::BasicEdge;
     use crate::structure::todo::Todo;
     use crate::structure::todo::Todo::{Blossom, Vertex};
+    use num::BigInt;
+    use num::rational::Ratio;
 
     fn vertex(w: u64) -> Todo<u64, BasicEdge<u64>> {
         Vertex(w, 0)
@@ -80,4 +84,19 @@ mod test_todo {
         assert!( blossom(17) > blossom(12) );
         assert!( blossom(12) >= blossom(12) );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_exact_todo() {
+        // 0.1 + 0.2 is slightly more than 0.3 as an f64, which would put this vertex after the blossom instead of before it.
+        let tenth = |n: i64| Ratio::new(n, 10);
+        let vertex: Todo<Ratio<i64>, BasicEdge<Ratio<i64>>> = Vertex(tenth(1) + tenth(2), 0);
+        let blossom = Blossom(tenth(6), BasicEdge::new(0, 0, tenth(0)));
+        assert!( vertex < blossom );
+        assert!( blossom > vertex );
+
+        let huge = BigInt::from(u64::MAX) * BigInt::from(u64::MAX);
+        let vertex: Todo<BigInt, BasicEdge<BigInt>> = Vertex(huge.clone(), 0);
+        assert!( vertex < Blossom(huge.clone() * BigInt::from(2), BasicEdge::new(0, 0, BigInt::from(0))) );
+        assert!( vertex > Blossom(huge * BigInt::from(2) - BigInt::from(1), BasicEdge::new(0, 0, BigInt::from(0))) );
+    }
+}
@@ -3,6 +3,8 @@ use std::cmp::Ordering::Equal;
 use std::ops::{Add, Div, Sub};
 use std::str::FromStr;
 use std::fmt::{Debug, Display};
+use num::{BigInt, BigUint, One, Zero};
+use num::rational::Ratio;
 
 pub trait Weight:
     Add<Output = Self> +
@@ -11,12 +13,13 @@ pub trait Weight:
     Clone +
     PartialEq +
     FromStr<Err: Display + Debug> +
-    From<u32> +
+    Zero +
+    One +
     PartialOrd +
-    Copy +
     Default +
     Debug +
     Display +
+    Overflowing +
 {}
 
 impl<T> Weight for T
@@ -28,14 +31,34 @@ impl<T> Weight for T
             Clone +
             PartialEq +
             FromStr<Err: Display + Debug> +
-            From<u32> +
+            Zero +
+            One +
             PartialOrd +
-            Copy +
             Default +
             Debug +
-            Display,
+            Display +
+            Overflowing,
 {}
 
+/**
+A number that may be able to tell that its arithmetic overflowed, so that the algorithms can report it instead of returning a wrong answer.
+Only Checked ever overflows, every other number type keeps the default.
+*/
+pub trait Overflowing {
+    fn is_overflow(&self) -> bool { false }
+}
+
+macro_rules! never_overflowing {
+    ($($t:ty),*) => { $(impl Overflowing for $t {})* };
+}
+
+never_overflowing!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, BigInt, BigUint);
+
+impl <T> Overflowing for Ratio<T> {}
+
+#[cfg(feature = "decimal")]
+impl Overflowing for rust_decimal::Decimal {}
+
 #[derive(PartialEq, PartialOrd)]
 pub struct Order<T: PartialOrd + PartialEq>(pub T);
 
@@ -46,6 +69,30 @@ impl <T: PartialOrd + PartialEq> Ord for Order<T> {
 }
 impl <T: PartialOrd + PartialEq> Eq for Order<T> {}
 
+/**
+Check that a weight can be the cost of an edge, which means that it is a number that is neither negative nor infinite.
+The algorithms can handle edges that cost nothing, but they are only allowed when asked for.
+Returns what is wrong with the weight otherwise.
+*/
+pub fn check_weight<W: Weight>(w: &W, zero_weights: bool) -> Result<(), &'static str> {
+    if w.partial_cmp(w).is_none() {
+        Err("not a number")
+    }
+    else if *w < W::zero() {
+        Err("negative")
+    }
+    // Infinity minus itself is not a number, while every finite value cancels out.
+    else if w.clone() - w.clone() != W::zero() {
+        Err("infinite")
+    }
+    else if w.is_zero() && ! zero_weights {
+        Err("zero, which is only allowed if zero weights are enabled")
+    }
+    else {
+        Ok(())
+    }
+}
+
 pub trait Weighted<W: Weight> {
     fn weight(&self) -> W;
 }
\ No newline at end of file
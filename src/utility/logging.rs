@@ -0,0 +1,44 @@
+/*!
+Logging macros that forward to the `log` facade when the `log` feature is enabled, and compile to nothing otherwise.
+
+The target of every message is the module it was logged from, such as `shortest_odd_path::algorithm::shortest_odd_path`,
+so a logger like `env_logger` can filter them per module:
+`RUST_LOG=shortest_odd_path::algorithm::shortest_odd_path=trace`.
+
+The message is only formatted if a logger actually wants it.
+*/
+
+#[cfg(feature = "log")]
+pub(crate) use log as __log;
+
+#[cfg(feature = "log")]
+macro_rules! __log_at {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::utility::logging::__log::log!($crate::utility::logging::__log::Level::$level, $($arg)+)
+    };
+}
+
+// Type check the arguments, but never evaluate them.
+#[cfg(not(feature = "log"))]
+macro_rules! __log_at {
+    ($level:ident, $($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::utility::logging::__log_at!(Trace, $($arg)+) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::utility::logging::__log_at!(Debug, $($arg)+) };
+}
+
+// Named after the level, but it can't be called warn where it is defined, since that is also a built-in attribute.
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::utility::logging::__log_at!(Warn, $($arg)+) };
+}
+
+pub(crate) use {__log_at, trace, debug, log_warn as warn};
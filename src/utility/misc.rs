@@ -8,13 +8,6 @@ pub fn repeat<E>(n: usize, e: E) -> Vec<E>
     (0..n).map(|_| e.clone()).collect()
 }
 
-const DEBUG_MODE: bool = false;
-pub fn debug(s: String) {
-    if DEBUG_MODE {
-        println!("{}", s);
-    }
-}
-
 pub fn assert_is_path<W: Weight, E: Edge<W>>(path: &Vec<E>) {
     if let Some(f) = path.first() {
         let mut visited = BTreeSet::new();
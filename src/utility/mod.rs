@@ -1 +1,3 @@
 pub mod misc;
+pub mod logging;
+pub mod recorder;
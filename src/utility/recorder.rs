@@ -0,0 +1,142 @@
+/*!
+An observer that keeps every event of a search, so that it can be written out as JSON and replayed in a visualizer.
+
+The trace is a JSON array with one object per event, in order, such as
+`{"event": "d_minus", "vertex": 3, "value": 5, "pred": {"from": 1, "to": 3, "weight": 2}}`.
+Infinite costs are written as null, and weights that are not plain numbers are written as strings.
+*/
+use std::fmt::Write;
+use crate::structure::cost::Cost::{self, *};
+use crate::structure::event::{Event::{self, *}, Observer};
+use crate::structure::graph::edge::Edge;
+use crate::structure::weight::Weight;
+
+#[derive(Debug, Clone)]
+pub struct Recorder<W: Weight, E: Edge<W>> {
+    pub events: Vec<Event<W,E>>,
+}
+
+impl <W: Weight, E: Edge<W>> Recorder<W,E> {
+    pub fn new() -> Self {
+        Recorder {
+            events: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, event) in self.events.iter().enumerate() {
+            json.push_str(if i == 0 { "\n  " } else { ",\n  " });
+            json.push_str(&event_json(event));
+        }
+        json.push_str("\n]\n");
+        json
+    }
+}
+
+impl <W: Weight, E: Edge<W>> Default for Recorder<W,E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <W: Weight, E: Edge<W>> Observer<W,E> for Recorder<W,E> {
+    fn observe(&mut self, event: Event<W,E>) {
+        self.events.push(event);
+    }
+}
+
+fn event_json<W: Weight, E: Edge<W>>(event: &Event<W,E>) -> String {
+    let mut json = String::new();
+    let _ = match event {
+        Start {n, s, t} => write!(json, r#"{{"event": "start", "n": {}, "s": {}, "t": {}}}"#, n, s, t),
+        Scan {vertex} => write!(json, r#"{{"event": "scan", "vertex": {}}}"#, vertex),
+        DPlus {vertex, value} => write!(json, r#"{{"event": "d_plus", "vertex": {}, "value": {}}}"#, vertex, cost_json(value)),
+        DMinus {vertex, value, pred} => write!(json, r#"{{"event": "d_minus", "vertex": {}, "value": {}, "pred": {}}}"#, vertex, cost_json(value), edge_json(pred)),
+        Blossom {edge, base, bases} => write!(json, r#"{{"event": "blossom", "edge": {}, "base": {}, "bases": {:?}}}"#, edge_json(edge), base, bases),
+        SetBase {vertex, base} => write!(json, r#"{{"event": "set_base", "vertex": {}, "base": {}}}"#, vertex, base),
+        Done {cost} => write!(json, r#"{{"event": "done", "cost": {}}}"#, cost_json(cost)),
+    };
+    json
+}
+
+fn edge_json<W: Weight, E: Edge<W>>(e: &E) -> String {
+    format!(r#"{{"from": {}, "to": {}, "weight": {}}}"#, e.from(), e.to(), weight_json(e.weight()))
+}
+
+fn cost_json<W: Weight>(cost: &Cost<W>) -> String {
+    match cost {
+        Infinite => String::from("null"),
+        Finite(w) => weight_json(w.clone()),
+    }
+}
+
+fn weight_json<W: Weight>(w: W) -> String {
+    let text = w.to_string();
+    match text.parse::<f64>() {
+        Ok(x) if x.is_finite() => text,
+        _ => format!("{:?}", text),
+    }
+}
+
+#[cfg(test)]
+mod test_recorder {
+    use crate::algorithm::shortest_odd_path::{shortest_odd_path, shortest_odd_path_observed};
+    use crate::structure::graph::edge::BasicEdge;
+    use crate::structure::graph::undirected_graph::UndirectedGraph;
+    use crate::structure::path_result::PathResult::*;
+    use super::*;
+
+    fn read(name: &str) -> UndirectedGraph<u64, BasicEdge<u64>> {
+        std::fs::read_to_string(["data/small_graphs/", name, "/", name, ".in"].concat()).unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn test_recorded_search() {
+        let graph = read("small1");
+        let mut recorder = Recorder::new();
+        let result = shortest_odd_path_observed(&graph, 0, 4, &mut recorder);
+        let cost = match (&result, shortest_odd_path(&graph, 0, 4)) {
+            (Possible {cost, ..}, Possible {cost: expected, ..}) if *cost == expected => *cost,
+            _ => panic!("Observing the search changed the result"),
+        };
+
+        assert_eq!(recorder.events.first(), Some(&Start {n: 5, s: 0, t: 4}));
+        assert_eq!(recorder.events.last(), Some(&Done {cost: Finite(cost)}));
+        // Replaying the trace must give t the same distance as the search found.
+        let d_minus_t = recorder.events.iter()
+            .filter_map(|event| match event {
+                DMinus {vertex: 4, value, ..} => Some(*value),
+                _ => None,
+            })
+            .last();
+        assert_eq!(d_minus_t, Some(Finite(cost)));
+
+        let json = recorder.to_json();
+        assert!(json.starts_with("[\n  {\"event\": \"start\", \"n\": 5, \"s\": 0, \"t\": 4},"));
+        assert!(json.ends_with(&format!("{{\"event\": \"done\", \"cost\": {}}}\n]\n", cost)));
+        assert_eq!(json.lines().count(), recorder.events.len() + 2);
+    }
+
+    #[test]
+    fn test_blossom_events() {
+        // The only odd path goes around the triangle 1, 2, 3, so the search has to form it as a blossom.
+        let mut graph: UndirectedGraph<u64, BasicEdge<u64>> = UndirectedGraph::new(6);
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (1, 3), (3, 4), (4, 5)] {
+            graph.add_edge(BasicEdge::new(u, v, 1));
+        }
+        let mut recorder = Recorder::new();
+        shortest_odd_path_observed(&graph, 0, 5, &mut recorder);
+        assert!(recorder.events.iter().any(|event| matches!(event, Blossom {..})));
+        assert!(recorder.events.iter().any(|event| matches!(event, SetBase {..})));
+        assert!(recorder.to_json().contains("\"event\": \"blossom\""));
+        assert_eq!(recorder.events.last(), Some(&Done {cost: Finite(5)}));
+    }
+
+    #[test]
+    fn test_json_values() {
+        assert_eq!(cost_json::<f64>(&Infinite), "null");
+        assert_eq!(cost_json(&Finite(2.5)), "2.5");
+        assert_eq!(edge_json(&BasicEdge::new(1, 2, 3u64)), r#"{"from": 1, "to": 2, "weight": 3}"#);
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use shortest_odd_path::algorithm::k_shortest_odd_paths::k_shortest_odd_paths;
+use shortest_odd_path::structure::graph::edge::BasicEdge;
+use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+use shortest_odd_path::structure::path_result::PathResult;
+use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+use shortest_odd_path::structure::weight::Weight;
+use shortest_odd_path::utility::misc::assert_is_path;
+use crate::utility::{Problem, verify_path};
+
+mod utility;
+
+pub struct KShortestOddPaths;
+impl <W> Problem<W> for KShortestOddPaths
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+{
+    type Output = Vec<PathResult<W,BasicEdge<W>>>;
+    type Query = (usize, usize, usize);
+    type Expected = Vec<W>;
+    type GraphClass = UndirectedGraph<W,BasicEdge<W>>;
+    fn name() -> String { String::from("kpaths") }
+    fn parse_query(query: &str) -> Option<(Self::Query, Option<Self::Expected>)> {
+        let mut words = query.split(' ');
+        let source = words.next()?.parse().ok()?;
+        let sink = words.next()?.parse().ok()?;
+        let k = words.next()?.parse().ok()?;
+        let costs = words.map(|w| w.parse().ok()).collect::<Option<Vec<W>>>()?;
+        Some(((source,sink,k), Some(costs)))
+    }
+    fn verify_answer(graph: &Self::GraphClass, query: &Self::Query, expected: &Option<Self::Expected>, actual: &Self::Output) {
+        let (source, sink, k) = query;
+        let context = format!("The {} shortest odd paths from {} to {}:", k, source, sink);
+        let mut costs = Vec::new();
+        let mut paths: Vec<&Vec<BasicEdge<W>>> = Vec::new();
+        for result in actual {
+            match result {
+                Impossible => panic!("{}\nThe enumerator returned an Impossible instead of stopping", context),
+                Possible {cost, path} => {
+                    assert_eq!(path.len() % 2, 1);
+                    verify_path::<W,BasicEdge<W>,Self>(graph, &context, cost.clone(), path, *source, *sink);
+                    assert_is_path(path);
+                    assert!( ! paths.contains(&path), "{}\nThe path {:?} was found twice", context, path);
+                    paths.push(path);
+                    costs.push(cost.clone());
+                }
+            }
+        }
+        assert!(costs.windows(2).all(|w| w[0] <= w[1]), "{}\nThe paths are not in order of increasing cost: {:?}", context, costs);
+        if let Some(exp) = expected {
+            assert_eq!(exp, &costs, "{}\nThe costs don't match: expected {:?}, but got {:?}", context, exp, costs);
+        }
+    }
+    fn compute(graph: &Self::GraphClass, (source, sink, k): &Self::Query) -> Self::Output {
+        k_shortest_odd_paths(graph, *source, *sink, *k).collect()
+    }
+}
+
+mod test_k_shortest_odd_paths {
+    use shortest_odd_path::algorithm::k_shortest_odd_paths::k_shortest_odd_paths;
+    use shortest_odd_path::structure::graph::edge::BasicEdge;
+    use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+    use shortest_odd_path::structure::path_result::PathResult::Possible;
+    use crate::KShortestOddPaths;
+    use crate::utility::meta_test;
+
+    fn test_paths(folder: &str, name: &str) {
+        meta_test::<KShortestOddPaths, u64>(folder, name)
+    }
+
+    // Parallel edges of the same weight are the same path whatever their labels, but a parallel edge of another weight is a path of its own.
+    #[test]
+    fn parallel_edges() {
+        let graph: UndirectedGraph<u64, BasicEdge<u64>> = "4\n0 1 1\n0 1 1\n0 1 1 1\n0 1 2\n1 2 1\n1 2 1 1\n0 2 3\n2 3 1".parse().unwrap();
+        let costs = |s: usize, t: usize| -> Vec<u64> {
+            k_shortest_odd_paths(&graph, s, t, 10)
+                .map(|result| match result {
+                    Possible {cost, ..} => cost,
+                    _ => panic!("The enumerator returned an Impossible instead of stopping"),
+                })
+                .collect()
+        };
+        assert_eq!(vec![1, 2], costs(0, 1));
+        assert_eq!(vec![3, 4], costs(0, 3));
+    }
+
+    mod small_paths {
+        use crate::test_k_shortest_odd_paths::test_paths;
+
+        fn test(name: &str) { test_paths("small_graphs", name); }
+
+        #[test]
+        fn small1() { test("small1"); }
+        #[test]
+        fn small2() { test("small2"); }
+        #[test]
+        fn small3() { test("small3"); }
+        #[test]
+        fn small5() { test("small5"); }
+        #[test]
+        fn small6() { test("small6"); }
+        #[test]
+        fn small7() { test("small7"); }
+    }
+    mod medium_paths {
+        use crate::test_k_shortest_odd_paths::test_paths;
+
+        fn test(name: &str) { test_paths("medium_graphs", name); }
+
+        #[test]
+        fn medium1() { test("medium1"); }
+        #[test]
+        fn medium2() { test("medium2"); }
+        #[test]
+        fn medium3() { test("medium3"); }
+    }
+    mod special_paths {
+        use crate::test_k_shortest_odd_paths::test_paths;
+
+        fn test(name: &str) { test_paths("special_graphs", name); }
+
+        #[test]
+        fn gamma4() { test("gamma4"); }
+        #[test]
+        fn petersen() { test("petersen"); }
+    }
+}
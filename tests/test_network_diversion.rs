@@ -1,9 +1,8 @@
 use std::fmt::{Debug, Display};
 use std::str::FromStr;
-use shortest_odd_path::algorithm::network_diversion::network_diversion;
+use shortest_odd_path::algorithm::network_diversion::{network_diversion, DiversionResult::{self, *}};
 use shortest_odd_path::algorithm::shortest_path::bfs;
 use shortest_odd_path::structure::cost::{Cost, Cost::*};
-use shortest_odd_path::structure::graph::planar_edge::PlanarEdge;
 use shortest_odd_path::structure::graph::planar_graph::PlanarGraph;
 use shortest_odd_path::structure::weight::Weight;
 use utility::Problem;
@@ -16,7 +15,7 @@ impl <W> Problem<W> for NetworkDiversion
     where W: Weight,
           <W as FromStr>::Err: Debug + Display,
 {
-    type Output = Option<(W, Vec<PlanarEdge<W>>)>;
+    type Output = DiversionResult<W>;
     type Query = (usize,usize,(usize,usize));
     type Expected = Cost<W>;
     type GraphClass = PlanarGraph<W>;
@@ -39,15 +38,15 @@ impl <W> Problem<W> for NetworkDiversion
         let context = format!("Network Diversion from {} to {}, every path must go through ({},{}):", s,t,du,dv);
         if let Some(exp) = expected {
             match (exp, out) {
-                (Infinite, Some(_)) => panic!("{}\nNo diversion is supposed to be possible here, but we found one anyway?", context),
-                (Finite(_), None) => panic!("{}\nWe could not find any diversions, even though it should be possible", context),
-                (Finite(exp_cost), Some((actual_cost, _))) => {
+                (Infinite, Diverted {..}) => panic!("{}\nNo diversion is supposed to be possible here, but we found one anyway?", context),
+                (Finite(_), Undivertable {reason}) => panic!("{}\nWe could not find any diversions, even though it should be possible: {}", context, reason),
+                (Finite(exp_cost), Diverted {cost: actual_cost, ..}) => {
                     assert_eq!(exp_cost, actual_cost, "{}\nThe expected and actual costs do not match: {} != {}", context, exp_cost, actual_cost);
                 }
                 _ => {},
             }
         }
-        if let Some((_, diversion)) = out {
+        if let Diverted {cost: _, diversion} = out {
             let mut g = planar.real().clone();
             let mut bottleneck = g.find_edges(du, dv);
 
@@ -67,6 +66,7 @@ impl <W> Problem<W> for NetworkDiversion
 
     fn compute(graph: &Self::GraphClass, &(s,t,(u,v)): &Self::Query) -> Self::Output {
         network_diversion(graph, s, t,(u,v))
+            .unwrap_or_else(|err| panic!("Network Diversion from {} to {} through ({},{}) failed: {}", s, t, u, v, err))
     }
 }
 
@@ -119,4 +119,78 @@ mod test_network_diversion {
             test_delaunay(50);
         }
     }
+
+    mod checked_diversions {
+        use shortest_odd_path::algorithm::network_diversion::{network_diversion, DiversionResult::*, NoDiversion::*};
+        use shortest_odd_path::structure::checked::{Checked, Checked::Value};
+        use shortest_odd_path::structure::error::Error::Overflow;
+        use shortest_odd_path::structure::graph::planar_graph::PlanarGraph;
+
+        // A square, where the cheapest diversion through (0,1) is one of the two edges on the other side.
+        fn square(weight: u8) -> PlanarGraph<Checked<u8>> {
+            format!("4 4\n0 0 0\n1 2 0\n2 2 2\n3 0 2\n0 1 1\n1 2 1\n2 3 {}\n3 0 {}", weight, weight).parse().unwrap()
+        }
+
+        // Two ways around (0,1) between 0 and 2, so the diversion has to cut both of them.
+        fn double_square(weight: u8) -> PlanarGraph<Checked<u8>> {
+            format!("5 6\n0 0 0\n1 1 1\n2 2 0\n3 1 -1\n4 1 -2\n0 1 1\n1 2 1\n0 3 {}\n3 2 {}\n0 4 {}\n4 2 {}", weight, weight, weight, weight).parse().unwrap()
+        }
+
+        #[test]
+        fn overflow() {
+            for weight in [100, 200] {
+                match network_diversion(&square(weight), 0, 2, (0, 1)) {
+                    Ok(Diverted {cost, diversion}) => {
+                        assert_eq!(Value(weight), cost);
+                        assert_eq!(1, diversion.len());
+                    }
+                    other => panic!("Expected a diversion of cost {}, but got {:?}", weight, other),
+                }
+            }
+            match network_diversion(&double_square(100), 0, 2, (0, 1)) {
+                Ok(Diverted {cost, diversion}) => {
+                    assert_eq!(Value(200), cost);
+                    assert_eq!(2, diversion.len());
+                }
+                other => panic!("Expected a diversion of cost 200, but got {:?}", other),
+            }
+            assert_eq!(Err(Overflow), network_diversion(&double_square(200), 0, 2, (0, 1)).map(|_| ()));
+        }
+
+        #[test]
+        fn undivertable() {
+            assert_eq!(Ok(Undivertable {reason: SameVertex}), network_diversion(&square(1), 2, 2, (0, 1)));
+            // No path from 0 to 1 goes out to the pendant vertex 2.
+            let pendant: PlanarGraph<Checked<u8>> = "3 2\n0 0 0\n1 1 0\n2 2 0\n0 1 1\n1 2 1".parse().unwrap();
+            assert_eq!(Ok(Undivertable {reason: NoPathThrough}), network_diversion(&pendant, 0, 1, (1, 2)));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    mod batch_diversions {
+        use shortest_odd_path::algorithm::batch::network_diversion_batch;
+        use shortest_odd_path::algorithm::network_diversion::network_diversion;
+        use crate::NetworkDiversion;
+        use crate::utility::load_test;
+
+        fn test_batch(folder: &str, name: &str) {
+            let (planar, queries) = load_test::<NetworkDiversion, f64, _>(folder, name, str::parse);
+            let queries: Vec<(usize, usize, (usize, usize))> = queries.into_iter().map(|(query, _)| query).collect();
+
+            let results = network_diversion_batch(&planar, &queries);
+            assert_eq!(queries.len(), results.len());
+            for (&(s, t, d), result) in queries.iter().zip(results) {
+                let expected = network_diversion(&planar, s, t, d).unwrap().cost();
+                assert_eq!(expected, result.unwrap().cost(), "The batch found another diversion from {} to {} through {:?} in {}", s, t, d, name);
+            }
+        }
+
+        #[test]
+        fn batches() {
+            for name in ["small_planar1", "small_planar2", "small_planar3", "small_planar4", "small_planar5"] {
+                test_batch("planar_graphs/small_planar_graphs", name);
+            }
+            test_batch("delaunay_graphs/planar_delaunay_graphs", "delaunay50");
+        }
+    }
 }
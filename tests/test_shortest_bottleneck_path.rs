@@ -46,13 +46,14 @@ impl <W> Problem<W> for ShortestBottleneckPath
             }
         }
         if let Possible {cost, path} = actual {
-            verify_path::<W,BasicEdge<W>,Self>(graph, &context, *cost, path, *source, *sink);
+            verify_path::<W,BasicEdge<W>,Self>(graph, &context, cost.clone(), path, *source, *sink);
             assert!(path.iter().find(|e| e.from() == *u && e.to() == *v).is_some(), "{}\nThe path was supposed to go through the bottleneck of ({},{}), but it doesn't.", context, u, v);
         }
     }
 
     fn compute(graph: &Self::GraphClass, (source, sink, (u,v)): &Self::Query) -> Self::Output {
         shortest_bottleneck_path::<W,BasicEdge<W>>(graph, *source, *sink, (*u,*v))
+            .unwrap_or_else(|err| panic!("Bottlenecked path from {} to {} through ({},{}) failed: {}", source, sink, u, v, err))
     }
 }
 
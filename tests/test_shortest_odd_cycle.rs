@@ -0,0 +1,186 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use shortest_odd_path::algorithm::shortest_odd_cycle::{shortest_odd_cycle_through_edge, shortest_odd_cycle_through_vertex};
+use shortest_odd_path::structure::cost::{Cost, Finite, Infinite};
+use shortest_odd_path::structure::graph::edge::{BasicEdge, Edge};
+use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+use shortest_odd_path::structure::path_result::PathResult;
+use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+use shortest_odd_path::structure::weight::Weight;
+use shortest_odd_path::utility::misc::assert_is_path;
+use crate::utility::{Problem, verify_path};
+
+mod utility;
+
+fn verify_cycle<W, Pr>(graph: &UndirectedGraph<W,BasicEdge<W>>, context: &String, expected: &Option<Cost<W>>, actual: &PathResult<W,BasicEdge<W>>, start: usize)
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+          Pr: Problem<W>,
+{
+    if let Some(exp) = expected {
+        match (exp, actual) {
+            (Infinite, Possible {cost: _, path}) => panic!("{}\nExpected to not find any odd cycle, but found one anyway: {:?}", context, path),
+            (Finite(cost), Impossible) => panic!("{}\nExpected the alg to find an odd cycle of cost {}, but it did not", context, cost),
+            (Finite(expected_cost), Possible {cost: actual_cost, path}) => {
+                assert_eq!(expected_cost, actual_cost, "{}\nThe costs don't match: expected {}, but got {}.\nThe offending cycle: {:?}", context, expected_cost, actual_cost, path);
+            },
+            _ => {}
+        }
+    }
+    if let Possible {cost, path} = actual {
+        assert_eq!(path.len() % 2, 1, "{}\nThe cycle {:?} is not odd", context, path);
+        verify_path::<W,BasicEdge<W>,Pr>(graph, context, cost.clone(), path, start, start);
+        assert_is_path(&path[1..].to_vec());
+    }
+}
+
+pub struct VertexCycle;
+impl <W> Problem<W> for VertexCycle
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+{
+    type Output = PathResult<W,BasicEdge<W>>;
+    type Query = usize;
+    type Expected = Cost<W>;
+    type GraphClass = UndirectedGraph<W,BasicEdge<W>>;
+    fn name() -> String { String::from("vertexcycle") }
+    fn parse_query(query: &str) -> Option<(Self::Query, Option<Self::Expected>)> {
+        let mut words = query.split(' ');
+        let v = words.next()?.parse().ok()?;
+        Some((v, words.next().and_then(|w| w.parse().ok())))
+    }
+    fn verify_answer(graph: &Self::GraphClass, &v: &Self::Query, expected: &Option<Self::Expected>, actual: &Self::Output) {
+        let context = format!("Odd cycle through {}:", v);
+        verify_cycle::<W,Self>(graph, &context, expected, actual, v);
+    }
+    fn compute(graph: &Self::GraphClass, &v: &Self::Query) -> Self::Output {
+        shortest_odd_cycle_through_vertex(graph, v)
+            .unwrap_or_else(|err| panic!("Odd cycle through {} failed: {}", v, err))
+    }
+}
+
+pub struct EdgeCycle;
+impl <W> Problem<W> for EdgeCycle
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+{
+    type Output = PathResult<W,BasicEdge<W>>;
+    type Query = (usize, usize);
+    type Expected = Cost<W>;
+    type GraphClass = UndirectedGraph<W,BasicEdge<W>>;
+    fn name() -> String { String::from("edgecycle") }
+    fn parse_query(query: &str) -> Option<(Self::Query, Option<Self::Expected>)> {
+        let mut words = query.split(' ');
+        let u = words.next()?.parse().ok()?;
+        let v = words.next()?.parse().ok()?;
+        Some(((u, v), words.next().and_then(|w| w.parse().ok())))
+    }
+    fn verify_answer(graph: &Self::GraphClass, &(u, v): &Self::Query, expected: &Option<Self::Expected>, actual: &Self::Output) {
+        let context = format!("Odd cycle through the edge ({}, {}):", u, v);
+        verify_cycle::<W,Self>(graph, &context, expected, actual, u);
+        if let Possible {cost: _, path} = actual {
+            assert_eq!(v, path[0].to(), "{}\nThe cycle does not start with the edge: {:?}", context, path);
+        }
+    }
+    fn compute(graph: &Self::GraphClass, &(u, v): &Self::Query) -> Self::Output {
+        shortest_odd_cycle_through_edge(graph, (u, v))
+            .unwrap_or_else(|err| panic!("Odd cycle through ({}, {}) failed: {}", u, v, err))
+    }
+}
+
+mod test_shortest_odd_cycle {
+    use crate::{EdgeCycle, VertexCycle};
+    use crate::utility::meta_test;
+
+    fn test_cycle(folder: &str, name: &str) {
+        meta_test::<VertexCycle, u64>(folder, name);
+        meta_test::<EdgeCycle, u64>(folder, name);
+    }
+
+    mod small_cycles {
+        use super::test_cycle;
+        fn test(name: &str) { test_cycle("small_graphs", name); }
+        #[test]
+        fn small1() { test("small1"); }
+        #[test]
+        fn small2() { test("small2"); }
+        #[test]
+        fn small3() { test("small3"); }
+        #[test]
+        fn small4() { test("small4"); }
+        #[test]
+        fn small5() { test("small5"); }
+        #[test]
+        fn small6() { test("small6"); }
+        #[test]
+        fn small7() { test("small7"); }
+    }
+    mod medium_cycles {
+        use super::test_cycle;
+        fn test(name: &str) { test_cycle("medium_graphs", name); }
+        #[test]
+        fn medium1() { test("medium1"); }
+        #[test]
+        fn medium2() { test("medium2"); }
+        #[test]
+        fn medium3() { test("medium3"); }
+        #[test]
+        fn medium4() { test("medium4"); }
+        #[test]
+        fn medium5() { test("medium5"); }
+        #[test]
+        fn medium6() { test("medium6"); }
+    }
+    mod large_cycles {
+        use super::test_cycle;
+        fn test(name: &str) { test_cycle("large_graphs", name); }
+        #[test]
+        fn large1() { test("large1"); }
+        #[test]
+        fn large2() { test("large2"); }
+    }
+    mod special_cycles {
+        use super::test_cycle;
+        fn test(name: &str) { test_cycle("special_graphs", name); }
+        #[test]
+        fn gamma4() { test("gamma4"); }
+        #[test]
+        fn petersen() { test("petersen"); }
+        #[test]
+        fn grid5() { test("grid5"); }
+    }
+    mod global_cycles {
+        use shortest_odd_path::algorithm::shortest_odd_cycle::shortest_odd_cycle;
+        use shortest_odd_path::structure::graph::edge::BasicEdge;
+        use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+        use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+
+        fn read(folder: &str, name: &str) -> UndirectedGraph<u64,BasicEdge<u64>> {
+            std::fs::read_to_string(["data/", folder, "/", name, "/", name, ".in"].concat())
+                .expect("Could not find the graph")
+                .parse()
+                .expect("Could not parse the graph")
+        }
+
+        #[test]
+        fn petersen_has_odd_girth_five() {
+            match shortest_odd_cycle(&read("special_graphs", "petersen")) {
+                Possible {cost: _, path} => assert_eq!(5, path.len()),
+                Impossible => panic!("The Petersen graph is not bipartite"),
+            }
+        }
+
+        #[test]
+        fn grid_is_bipartite() {
+            assert!(matches!(shortest_odd_cycle(&read("special_graphs", "grid5")), Impossible));
+        }
+
+        #[test]
+        fn medium1_has_a_triangle() {
+            match shortest_odd_cycle(&read("medium_graphs", "medium1")) {
+                Possible {cost, path} => assert_eq!((3, 3), (cost, path.len())),
+                Impossible => panic!("medium1 has an odd cycle"),
+            }
+        }
+    }
+}
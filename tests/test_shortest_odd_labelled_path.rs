@@ -0,0 +1,80 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use shortest_odd_path::algorithm::shortest_odd_labelled_path::shortest_odd_labelled_path;
+use shortest_odd_path::structure::cost::{Cost, Finite, Infinite};
+use shortest_odd_path::structure::graph::edge::{BasicEdge, Edge};
+use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+use shortest_odd_path::structure::path_result::PathResult;
+use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+use shortest_odd_path::structure::weight::Weight;
+use shortest_odd_path::utility::misc::assert_is_path;
+use crate::utility::{Problem, verify_path};
+
+mod utility;
+
+pub struct ShortestOddLabelledPath;
+impl <W> Problem<W> for ShortestOddLabelledPath
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+{
+    type Output = PathResult<W,BasicEdge<W>>;
+    type Query = (usize, usize);
+    type Expected = Cost<W>;
+    type GraphClass = UndirectedGraph<W,BasicEdge<W>>;
+    fn name() -> String { String::from("labelledpath") }
+    fn parse_query(query: &str) -> Option<(Self::Query, Option<Self::Expected>)> {
+        let mut words = query.split(' ');
+        let source = words.next()?.parse().ok()?;
+        let sink = words.next()?.parse().ok()?;
+        Some(((source, sink), words.next().and_then(|w| w.parse().ok())))
+    }
+    fn verify_answer(graph: &Self::GraphClass, &(source, sink): &Self::Query, expected: &Option<Self::Expected>, actual: &Self::Output) {
+        let context = format!("Odd labelled path from {} to {}:", source, sink);
+        if let Some(exp) = expected {
+            match (exp, actual) {
+                (Infinite, Possible {cost: _, path}) => panic!("{}\nExpected to not find any {}-{}-path, but found one anyway: {:?}", context, source, sink, path),
+                (Finite(cost), Impossible) => panic!("{}\nExpected the alg to find an {}-{}-path of cost {}, but it did not", context, source, sink, cost),
+                (Finite(expected_cost), Possible {cost: actual_cost, path}) => {
+                    assert_eq!(expected_cost, actual_cost, "{}\nThe costs don't match: expected {}, but got {}.\nThe offending path: {:?}", context, expected_cost, actual_cost, path);
+                },
+                _ => {}
+            }
+        }
+        if let Possible {cost, path} = actual {
+            verify_path::<W,BasicEdge<W>,Self>(graph, &context, cost.clone(), path, source, sink);
+            assert_is_path(path);
+            for e in path {
+                assert!(graph[e.from()].contains(e), "{}\nThe path uses the edge {:?}, which is not in the graph with that label", context, e);
+            }
+            let labels = path.iter().filter(|e| e.label()).count();
+            assert_eq!(labels % 2, 1, "{}\nThe path {:?} uses an even number of labelled edges", context, path);
+        }
+    }
+    fn compute(graph: &Self::GraphClass, &(source, sink): &Self::Query) -> Self::Output {
+        shortest_odd_labelled_path(graph, source, sink)
+    }
+}
+
+mod test_shortest_odd_labelled_path {
+    use crate::ShortestOddLabelledPath;
+    use crate::utility::meta_test;
+
+    fn test(name: &str) {
+        meta_test::<ShortestOddLabelledPath, u64>("labelled_graphs", name);
+    }
+
+    #[test]
+    fn labelled1() { test("labelled1"); }
+    #[test]
+    fn labelled2() { test("labelled2"); }
+    #[test]
+    fn labelled3() { test("labelled3"); }
+    #[test]
+    fn labelled4() { test("labelled4"); }
+    #[test]
+    fn labelled5() { test("labelled5"); }
+
+    // Every edge is labelled, so this is the ordinary shortest odd path in small1.
+    #[test]
+    fn labelled6() { test("labelled6"); }
+}
@@ -1,17 +1,64 @@
 use std::fmt::{Debug, Display};
 use std::str::FromStr;
-use shortest_odd_path::algorithm::shortest_odd_path::shortest_odd_path;
+use shortest_odd_path::algorithm::shortest_odd_path::{explain_impossible, shortest_odd_path, shortest_odd_path_with_basis};
+use shortest_odd_path::structure::basis::ObserverBase;
+use shortest_odd_path::structure::infeasibility::{Infeasibility, Infeasibility::*};
 use shortest_odd_path::structure::cost::{Cost, Finite, Infinite};
-use shortest_odd_path::structure::graph::edge::BasicEdge;
+use shortest_odd_path::structure::graph::edge::{BasicEdge, Edge};
 use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
 use shortest_odd_path::structure::path_result::PathResult;
 use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
 use shortest_odd_path::structure::weight::Weight;
-use shortest_odd_path::utility::misc::{assert_is_path, debug};
+use shortest_odd_path::utility::misc::assert_is_path;
 use crate::utility::{Problem, verify_path};
 
 mod utility;
 
+// Check that the certificate really rules out every odd path.
+fn verify_infeasibility<W: Weight>(graph: &UndirectedGraph<W,BasicEdge<W>>, context: &String, certificate: &Infeasibility, source: usize, sink: usize) {
+    match certificate {
+        Disconnected {component} => {
+            assert!(component.contains(&source) && ! component.contains(&sink), "{}\nThe component {:?} does not separate {} from {}", context, component, source, sink);
+            for &u in component {
+                for e in &graph[u] {
+                    assert!(component.contains(&e.to()), "{}\nThe component {:?} is left by the edge {:?}", context, component, e);
+                }
+            }
+        }
+        EvenBlocks {colouring} => {
+            assert!(colouring[source].is_some() && colouring[source] == colouring[sink], "{}\n{} and {} do not have the same colour", context, source, sink);
+            for e in graph.edges() {
+                if let (Some(a), Some(b)) = (colouring[e.from()], colouring[e.to()]) {
+                    assert!(a != b || e.from() == e.to(), "{}\nBoth ends of the edge {:?} have the same colour", context, e);
+                }
+            }
+            // Every component outside the coloured vertices may only touch one of them.
+            let mut seen: Vec<bool> = colouring.iter().map(Option::is_some).collect();
+            for r in graph.vertices() {
+                if seen[r] {
+                    continue;
+                }
+                let (mut stack, mut touching) = (vec![r], Vec::new());
+                seen[r] = true;
+                while let Some(u) = stack.pop() {
+                    for e in &graph[u] {
+                        if colouring[e.to()].is_some() {
+                            touching.push(e.to());
+                        }
+                        else if ! seen[e.to()] {
+                            seen[e.to()] = true;
+                            stack.push(e.to());
+                        }
+                    }
+                }
+                touching.sort();
+                touching.dedup();
+                assert!(touching.len() <= 1, "{}\nThe component of {} touches several coloured vertices: {:?}", context, r, touching);
+            }
+        }
+    }
+}
+
 pub struct ShortestOddPath;
 impl <W> Problem<W> for ShortestOddPath
     where W: Weight,
@@ -48,11 +95,25 @@ impl <W> Problem<W> for ShortestOddPath
             }
         }
         if let Possible {cost, path} = actual {
-            debug(format!("Odd path found: {:?}", path));
             assert_eq!(path.len() % 2, 1);
-            verify_path::<W,BasicEdge<W>,Self>(graph, &context, *cost, path, *source, *sink);
+            verify_path::<W,BasicEdge<W>,Self>(graph, &context, cost.clone(), path, *source, *sink);
             assert_is_path(&path);
         }
+        // The choice of basis must not change what the algorithm finds.
+        match (actual, shortest_odd_path_with_basis::<ObserverBase,_,_,_>(graph, *source, *sink)) {
+            (Possible {cost, ..}, Possible {cost: other, path}) => {
+                assert_eq!(*cost, other, "{}\nThe ObserverBase found a path of another cost: {:?}", context, path);
+                verify_path::<W,BasicEdge<W>,Self>(graph, &context, other, &path, *source, *sink);
+            },
+            (Impossible, Impossible) => {},
+            _ => panic!("{}\nThe UnionFindBase and the ObserverBase disagree on whether an odd path exists", context),
+        }
+        match (actual, explain_impossible(graph, *source, *sink)) {
+            (Possible {..}, Some(certificate)) => panic!("{}\nFound an odd path, but also a certificate that none exist: {:?}", context, certificate),
+            (Impossible, None) => panic!("{}\nFound no odd path, and could not explain why", context),
+            (Impossible, Some(certificate)) => verify_infeasibility(graph, &context, &certificate, *source, *sink),
+            _ => {}
+        }
 
     }
     fn compute(graph: &Self::GraphClass, (source, sink): &Self::Query) -> Self::Output {
@@ -61,12 +122,10 @@ impl <W> Problem<W> for ShortestOddPath
 }
 
 mod test_shortest_odd_path {
-    use shortest_odd_path::utility::misc::debug;
     use crate::ShortestOddPath;
     use crate::utility::meta_test;
 
     fn test_path(folder: &str, name: &str) {
-        debug(format!("{}/{}", folder, name));
         meta_test::<ShortestOddPath, u64>(folder, name)
     }
 
@@ -130,10 +189,78 @@ mod test_shortest_odd_path {
         fn petersen() { test("petersen"); }
         #[test]
         fn grid5() { test("grid5"); }
+        #[test]
+        fn parallel() { test("parallel"); }
     }
-    mod counter_paths {
+    mod blossom_paths {
         use crate::test_shortest_odd_path::test_path;
-        fn test(name: &str) { test_path("counter_graphs", name); }
+
+        // Random graphs where the shortest odd path goes around blossoms nested inside other blossoms.
+        fn test(name: &str) { test_path("blossom_graphs", name); }
+
+        #[test]
+        fn blossom1() { test("blossom1"); }
+        #[test]
+        fn blossom2() { test("blossom2"); }
+        #[test]
+        fn blossom3() { test("blossom3"); }
+    }
+    mod nested_blossoms {
+        use shortest_odd_path::algorithm::shortest_odd_path::shortest_odd_path;
+        use shortest_odd_path::structure::graph::edge::BasicEdge;
+        use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+        use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+        use shortest_odd_path::utility::misc::assert_is_path;
+
+        /*
+        The vertices 0..=k in a row, with an edge between each pair of neighbours, and a detour of two edges from every vertex
+        to the one after the next, except that the last detour goes to the neighbour instead. Only that last detour closes an
+        odd cycle, so the odd path from 0 to a leaf hanging off 1 winds all the way out to it and back, through k nested blossoms.
+        */
+        fn zigzag(k: usize) -> UndirectedGraph<u64, BasicEdge<u64>> {
+            let mut graph = UndirectedGraph::new(k + 1);
+            for i in 0..k {
+                graph.add_edge(BasicEdge::new(i, i + 1, 1));
+                let middle = graph.add_vertex();
+                graph.add_edge(BasicEdge::new(i, middle, 1));
+                graph.add_edge(BasicEdge::new(middle, (i + 2).min(k), 1));
+            }
+            let leaf = graph.add_vertex();
+            graph.add_edge(BasicEdge::new(1, leaf, 1));
+            graph
+        }
+
+        #[test]
+        fn deeply_nested() {
+            let k = 2000;
+            let graph = zigzag(k);
+            let leaf = graph.n() - 1;
+            // Unfolding the blossoms must not take a stack frame for each of them.
+            let result = std::thread::Builder::new()
+                .stack_size(128 * 1024)
+                .spawn(move || shortest_odd_path(&graph, 0, leaf))
+                .unwrap()
+                .join()
+                .unwrap();
+            match result {
+                Possible {cost, path} => {
+                    assert_eq!(2 * k as u64 + 1, cost);
+                    assert_eq!(2 * k + 1, path.len());
+                    assert_is_path(&path);
+                }
+                Impossible => panic!("Expected an odd path around all the blossoms"),
+            }
+        }
+    }
+    mod counter_paths {
+        use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+        use crate::ShortestOddPath;
+        use crate::utility::meta_test_with;
+
+        // The counterexamples rely on edges that cost nothing.
+        fn test(name: &str) {
+            meta_test_with::<ShortestOddPath, u64, _>("counter_graphs", name, UndirectedGraph::parse_with_zero_weights);
+        }
 
         #[test]
         fn counter50() { test("counter50"); }
@@ -146,4 +273,175 @@ mod test_shortest_odd_path {
         #[test]
         fn counter8() { test("counter8"); }
     }
+    mod exact_paths {
+        use num::BigInt;
+        use num::rational::Ratio;
+        use crate::ShortestOddPath;
+        use crate::utility::meta_test;
+
+        #[test]
+        fn big_integers() {
+            for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+                meta_test::<ShortestOddPath, BigInt>("small_graphs", name);
+            }
+            meta_test::<ShortestOddPath, BigInt>("large_graphs", "large1");
+        }
+        #[test]
+        fn rationals() {
+            for name in ["medium1", "medium2", "medium3"] {
+                meta_test::<ShortestOddPath, Ratio<i64>>("medium_graphs", name);
+            }
+            for name in ["fractional1", "fractional2", "fractional3"] {
+                meta_test::<ShortestOddPath, Ratio<i64>>("fractional_graphs", name);
+                meta_test::<ShortestOddPath, Ratio<BigInt>>("fractional_graphs", name);
+            }
+        }
+        #[cfg(feature = "decimal")]
+        #[test]
+        fn decimals() {
+            use rust_decimal::Decimal;
+            for name in ["decimal1", "decimal2"] {
+                meta_test::<ShortestOddPath, Decimal>("decimal_graphs", name);
+            }
+        }
+    }
+    mod checked_paths {
+        use shortest_odd_path::algorithm::shortest_odd_path::{shortest_odd_path, try_shortest_odd_path};
+        use shortest_odd_path::structure::checked::{Checked, Checked::Value};
+        use shortest_odd_path::structure::error::Error::{Overflow, VertexOutOfRange};
+        use shortest_odd_path::structure::graph::edge::BasicEdge;
+        use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+        use shortest_odd_path::structure::path_result::PathResult::Possible;
+        use crate::ShortestOddPath;
+        use crate::utility::meta_test;
+
+        fn line(weight: u8) -> UndirectedGraph<Checked<u8>, BasicEdge<Checked<u8>>> {
+            format!("4\n0 1 {}\n1 2 {}\n2 3 {}", weight, weight, weight).parse().unwrap()
+        }
+
+        #[test]
+        fn checked_graphs() {
+            for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+                meta_test::<ShortestOddPath, Checked<u64>>("small_graphs", name);
+            }
+            meta_test::<ShortestOddPath, Checked<u32>>("medium_graphs", "medium1");
+        }
+        #[test]
+        fn overflow() {
+            assert_eq!(Err(Overflow), try_shortest_odd_path(&line(100), 0, 3).map(|_| ()));
+            assert_eq!(Err(VertexOutOfRange {vertex: 4, n: 4}), try_shortest_odd_path(&line(1), 0, 4).map(|_| ()));
+            // Every cost fits in a u8, even though twice the cost of the path to 2 does not.
+            for (weight, expected) in [(40, 120), (70, 210), (85, 255)] {
+                match try_shortest_odd_path(&line(weight), 0, 3) {
+                    Ok(Possible {cost, path}) => {
+                        assert_eq!(Value(expected), cost);
+                        assert_eq!(3, path.len());
+                    }
+                    _ => panic!("Expected to find the path of cost {}", expected),
+                }
+            }
+        }
+        #[test]
+        #[should_panic]
+        fn overflow_panics() {
+            shortest_odd_path(&line(100), 0, 3);
+        }
+    }
+    mod solver_paths {
+        use shortest_odd_path::algorithm::shortest_odd_path::{shortest_odd_path, OddPathSolver};
+        use shortest_odd_path::structure::error::Error;
+        use shortest_odd_path::structure::error::Error::VertexOutOfRange;
+        use shortest_odd_path::structure::graph::edge::BasicEdge;
+        use shortest_odd_path::structure::graph::undirected_graph::UndirectedGraph;
+        use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+        use crate::ShortestOddPath;
+        use crate::utility::load_test;
+
+        // One solver has to answer every query exactly like a fresh search would, no matter what it was asked before.
+        fn test_solver(folder: &str, name: &str, parse: impl Fn(&str) -> Result<UndirectedGraph<u64, BasicEdge<u64>>, Error>) {
+            let (graph, queries) = load_test::<ShortestOddPath, u64, _>(folder, name, parse);
+            let mut queries: Vec<(usize,usize)> = queries.into_iter().map(|(query, _)| query).collect();
+            queries.extend(queries.clone().into_iter().rev());
+
+            let mut solver = OddPathSolver::new(&graph);
+            for (s, t) in queries {
+                match (solver.query(s, t), shortest_odd_path(&graph, s, t)) {
+                    (Impossible, Impossible) => {},
+                    (Possible {cost: a, path: p}, Possible {cost: b, path: q}) => {
+                        assert_eq!(a, b, "The solver found an odd {}-{}-path of another cost in {}", s, t, name);
+                        assert!(p == q, "The solver found another odd {}-{}-path in {}: {:?} != {:?}", s, t, name, p, q);
+                    },
+                    _ => panic!("The solver and shortest_odd_path disagree on whether an odd {}-{}-path exists in {}", s, t, name),
+                }
+            }
+            assert_eq!(Some(VertexOutOfRange {vertex: graph.n(), n: graph.n()}), solver.try_query(0, graph.n()).err());
+        }
+
+        #[test]
+        fn small_graphs() {
+            for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+                test_solver("small_graphs", name, str::parse);
+            }
+        }
+        #[test]
+        fn medium_graphs() {
+            for name in ["medium1", "medium2", "medium3", "medium4", "medium5", "medium6"] {
+                test_solver("medium_graphs", name, str::parse);
+            }
+        }
+        #[test]
+        fn special_graphs() {
+            for name in ["gamma4", "petersen", "grid5"] {
+                test_solver("special_graphs", name, str::parse);
+            }
+            for name in ["counter8", "counter10", "counter24"] {
+                test_solver("counter_graphs", name, UndirectedGraph::parse_with_zero_weights);
+            }
+        }
+    }
+    #[cfg(feature = "rayon")]
+    mod batch_paths {
+        use shortest_odd_path::algorithm::batch::shortest_odd_path_batch;
+        use shortest_odd_path::algorithm::shortest_odd_path::shortest_odd_path;
+        use shortest_odd_path::structure::error::Error::VertexOutOfRange;
+        use shortest_odd_path::structure::path_result::PathResult::{Impossible, Possible};
+        use crate::ShortestOddPath;
+        use crate::utility::load_test;
+
+        fn test_batch(folder: &str, name: &str) {
+            let (graph, queries) = load_test::<ShortestOddPath, u64, _>(folder, name, str::parse);
+            let mut queries: Vec<(usize,usize)> = queries.into_iter().map(|(query, _)| query).collect();
+            queries.push((0, graph.n()));
+
+            let results = shortest_odd_path_batch(&graph, &queries);
+            assert_eq!(queries.len(), results.len());
+            for (&(s, t), result) in queries.iter().zip(results) {
+                if t == graph.n() {
+                    assert_eq!(Some(VertexOutOfRange {vertex: t, n: graph.n()}), result.err());
+                    continue;
+                }
+                match (result.unwrap(), shortest_odd_path(&graph, s, t)) {
+                    (Impossible, Impossible) => {},
+                    (Possible {cost: a, ..}, Possible {cost: b, ..}) => assert_eq!(a, b, "The batch found an odd {}-{}-path of another cost in {}", s, t, name),
+                    _ => panic!("The batch and shortest_odd_path disagree on whether an odd {}-{}-path exists in {}", s, t, name),
+                }
+            }
+        }
+
+        #[test]
+        fn batches() {
+            for name in ["small1", "small2", "small3", "small4", "small5", "small6", "small7"] {
+                test_batch("small_graphs", name);
+            }
+            for name in ["medium1", "medium2", "medium3", "medium4", "medium5", "medium6"] {
+                test_batch("medium_graphs", name);
+            }
+            test_batch("large_graphs", "large1");
+        }
+        #[test]
+        fn empty_batch() {
+            let (graph, _) = load_test::<ShortestOddPath, u64, _>("small_graphs", "small1", str::parse);
+            assert!(shortest_odd_path_batch(&graph, &[]).is_empty());
+        }
+    }
 }
@@ -46,7 +46,7 @@ impl <W> Problem<W> for ShortestOddWalk
         }
         if let Possible { cost, path } = actual {
             assert_eq!(path.len() % 2, 1);
-            verify_path::<W, BasicEdge<W>, Self>(graph, &context, *cost, path, *source, *sink);
+            verify_path::<W, BasicEdge<W>, Self>(graph, &context, cost.clone(), path, *source, *sink);
         }
     }
     fn compute(graph: &Self::GraphClass, (source, sink): &Self::Query) -> Self::Output {
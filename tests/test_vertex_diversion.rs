@@ -0,0 +1,100 @@
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use shortest_odd_path::algorithm::network_diversion::{vertex_diversion, DiversionResult::{self, *}};
+use shortest_odd_path::algorithm::shortest_path::bfs;
+use shortest_odd_path::structure::cost::{Cost, Cost::*};
+use shortest_odd_path::structure::graph::edge::Edge;
+use shortest_odd_path::structure::graph::planar_graph::PlanarGraph;
+use shortest_odd_path::structure::weight::Weight;
+use utility::Problem;
+
+mod utility;
+
+pub struct VertexDiversion;
+
+impl <W> Problem<W> for VertexDiversion
+    where W: Weight,
+          <W as FromStr>::Err: Debug + Display,
+{
+    type Output = DiversionResult<W>;
+    type Query = (usize,usize,usize);
+    type Expected = Cost<W>;
+    type GraphClass = PlanarGraph<W>;
+
+    fn name() -> String {
+        "vertexdiversion".to_string()
+    }
+
+    fn parse_query(query: &str) -> Option<(Self::Query, Option<Self::Expected>)> {
+        let mut words = query.split(' ');
+        Some(((words.next()?.parse().ok()?,
+               words.next()?.parse().ok()?,
+               words.next()?.parse().ok()?,
+            ),
+              words.next().map(|w| Cost::from(w.parse()))
+        ))
+    }
+
+    fn verify_answer(planar: &Self::GraphClass, &(s,t,v): &Self::Query, expected: &Option<Self::Expected>, out: &Self::Output) {
+        let context = format!("Vertex Diversion from {} to {}, every path must go through {}:", s, t, v);
+        if let Some(exp) = expected {
+            match (exp, out) {
+                (Infinite, Diverted {..}) => panic!("{}\nNo diversion is supposed to be possible here, but we found one anyway?", context),
+                (Finite(_), Undivertable {reason}) => panic!("{}\nWe could not find any diversions, even though it should be possible: {}", context, reason),
+                (Finite(exp_cost), Diverted {cost, ..}) => {
+                    // The expected costs are rounded, so allow for some floating point errors.
+                    let difference = if exp_cost > cost { exp_cost.clone() - cost.clone() } else { cost.clone() - exp_cost.clone() };
+                    assert!(difference <= W::one() / W::from_str("1000000").unwrap(), "{}\nThe expected and actual costs do not match: {} != {}", context, exp_cost, cost);
+                }
+                _ => {},
+            }
+        }
+        if let Diverted {cost: _, diversion} = out {
+            let mut g = planar.real().clone();
+            assert!(! diversion.iter().any(|e| e.from() == v || e.to() == v), "{}\nAn edge around {} was deleted", context, v);
+            if bfs(&g, s)[t].is_infinite() || v == s || v == t {
+                return;
+            }
+            g.delete_edges(diversion);
+            let non_blocking = bfs(&g, s)[t].is_finite();
+            assert!(non_blocking, "{}\nThe diversion set is not minimal, and blocks off all {}-{}-paths.", context, s, t);
+
+            let around = g[v].clone();
+            g.delete_edges(&around);
+            let is_cut = bfs(&g, s)[t].is_infinite();
+            assert!(is_cut, "{}\nRemoving {} does not block all {}-{}-paths", context, v, s, t);
+        }
+    }
+
+    fn compute(graph: &Self::GraphClass, &(s,t,v): &Self::Query) -> Self::Output {
+        vertex_diversion(graph, s, t, v)
+            .unwrap_or_else(|err| panic!("Vertex Diversion from {} to {} through {} failed: {}", s, t, v, err))
+    }
+}
+
+#[cfg(test)]
+mod test_vertex_diversion {
+    mod small_planar_diversions {
+        use crate::VertexDiversion;
+        use crate::utility::meta_test;
+
+        fn test_diversion(name: &str) {
+            meta_test::<VertexDiversion, f64>("planar_graphs/small_planar_graphs", name);
+        }
+
+        #[test]
+        fn small_planar1() { test_diversion("small_planar1"); }
+
+        #[test]
+        fn small_planar2() { test_diversion("small_planar2"); }
+
+        #[test]
+        fn small_planar3() { test_diversion("small_planar3"); }
+
+        #[test]
+        fn small_planar4() { test_diversion("small_planar4"); }
+
+        #[test]
+        fn small_planar5() { test_diversion("small_planar5"); }
+    }
+}
@@ -10,8 +10,30 @@ pub fn meta_test<Pr, W: Weight>(folder: &str, name: &str)
           <<Pr as Problem<W>>::GraphClass as FromStr>::Err: Debug,
           W: Weight,
           <W as FromStr>::Err: Display + Debug,
+{
+    meta_test_with::<Pr, W, _>(folder, name, str::parse)
+}
+
+// The same as meta_test, but with another way to parse the graph, such as one that allows zero weights.
+pub fn meta_test_with<Pr, W: Weight, Err: Debug>(folder: &str, name: &str, parse: impl Fn(&str) -> Result<Pr::GraphClass, Err>)
+    where Pr: Problem<W>,
+          W: Weight,
+          <W as FromStr>::Err: Display + Debug,
 {
     println!("Testing {}...", name);
+    let (graph, queries) = load_test::<Pr, W, Err>(folder, name, parse);
+    for (query, expected) in queries {
+        Pr::verify_answer(&graph, &query, &expected, &Pr::compute(&graph, &query));
+    }
+    println!("Success :-)")
+}
+
+// Read the graph and the queries of a test, for tests that need all the queries at once instead of one at a time.
+pub fn load_test<Pr, W: Weight, Err: Debug>(folder: &str, name: &str, parse: impl Fn(&str) -> Result<Pr::GraphClass, Err>) -> (Pr::GraphClass, Vec<(Pr::Query, Option<Pr::Expected>)>)
+    where Pr: Problem<W>,
+          W: Weight,
+          <W as FromStr>::Err: Display + Debug,
+{
     let input_path = ["data/", folder, "/", name, "/", name, ".in"].concat();
     let query_path = ["data/", folder, "/", name, "/", name, ".", &Pr::name()].concat();
     let queries: Vec<(Pr::Query, Option<Pr::Expected>)> = std::fs::read_to_string(&query_path)
@@ -19,14 +41,10 @@ pub fn meta_test<Pr, W: Weight>(folder: &str, name: &str)
         .lines()
         .map(|line| Pr::parse_query(line).expect(format!("Could not parse this query: {}", line).as_str()))
         .collect();
-    let graph = std::fs::read_to_string(&input_path)
-        .expect(&format!("Could not find graph: {}", input_path))
-        .parse()
+    let graph = parse(&std::fs::read_to_string(&input_path)
+        .expect(&format!("Could not find graph: {}", input_path)))
         .expect("Could not parse the graph");
-    for (query, expected) in queries {
-        Pr::verify_answer(&graph, &query, &expected, &Pr::compute(&graph, &query));
-    }
-    println!("Success :-)")
+    (graph, queries)
 }
 
 pub trait Problem<W>
@@ -51,7 +69,7 @@ pub fn verify_path<'a, W, E, Pr>(graph: &UndirectedGraph<W,E>, context: &String,
 {
     assert_eq!(source, path[0].from(), "{}\nThe path starts at the wrong vertex! Expected {}, but yet it starts at {} for some reason", context, source, path[0].from());
     assert_eq!(sink, path[path.len()-1].to(), "{}\nThe path ends at the wrong vertex! Expected {}, but it ends at {} for some strange reason that you should consider debugging.", context, sink, path[path.len()-1].to());
-    let mut actual_cost = 0.into();
+    let mut actual_cost = W::zero();
     for e in path {
         assert!(graph.is_adjacent(e.from(), e.to()), "{}\nOur path includes an edge from {} to {} that doesn't exist in the graph!", context, e.from(), e.to());
         actual_cost = actual_cost + e.weight();